@@ -0,0 +1,302 @@
+//! Derive macros for the traits of `as_with_bytes`.
+//!
+//! `#[derive(AsBytes)]` implements `NoUninit`, which makes `AsBytes` available for a struct.
+//! `#[derive(WithBytes)]` implements `AnyBitPattern`, which makes `SafeWithBytes` available for a
+//! struct. Both derives check at compile time that:
+//!
+//! - the struct is `#[repr(C)]` or `#[repr(transparent)]`,
+//! - every field implements the derived marker trait, and
+//! - the struct contains no padding.
+//!
+//! The struct must also implement `Copy`. Enums, unions and generic structs are not supported.
+//!
+//! `#[derive(CheckedBitPattern)]` implements `CheckedBitPattern` for a fieldless enum with an
+//! integer representation such as `#[repr(u8)]`, so that `SafeWithBytes` can decode it after
+//! checking that the bytes hold one of its discriminants.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parenthesized, parse_macro_input, Data, DeriveInput, Error, Ident};
+
+/// Implements `NoUninit` for a struct, so that it can be converted into
+/// bytes with `AsBytes`.
+///
+/// ```rust
+/// use as_with_bytes::AsBytes;
+///
+/// #[derive(as_with_bytes_derive::AsBytes, Clone, Copy)]
+/// #[repr(C)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// assert_eq!(Point { x: 1, y: 2 }.as_bytes(), [1i32, 2].as_bytes());
+/// ```
+///
+/// Structs with padding are rejected:
+///
+/// ```compile_fail
+/// use as_with_bytes_derive::AsBytes;
+///
+/// #[derive(AsBytes, Clone, Copy)]
+/// #[repr(C)]
+/// struct Padded {
+///     small: u8,
+///     big: u32,
+/// }
+/// ```
+#[proc_macro_derive(AsBytes)]
+pub fn derive_as_bytes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, "AsBytes", "NoUninit", "assert_no_uninit")
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `AnyBitPattern` for a struct, so that it can be converted
+/// from bytes with `SafeWithBytes`.
+///
+/// ```rust
+/// use as_with_bytes::{AsBytes, SafeWithBytes};
+/// use as_with_bytes_derive::WithBytes;
+///
+/// #[derive(WithBytes, Clone, Copy, Debug, PartialEq)]
+/// #[repr(C)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let coords = [1i32, 2];
+/// assert_eq!(Point::safe_with_bytes(coords.as_bytes()), Ok(&Point { x: 1, y: 2 }));
+/// ```
+///
+/// Structs without a `C` or `transparent` representation are rejected:
+///
+/// ```compile_fail
+/// use as_with_bytes_derive::WithBytes;
+///
+/// #[derive(WithBytes, Clone, Copy)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+/// ```
+#[proc_macro_derive(WithBytes)]
+pub fn derive_with_bytes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, "WithBytes", "AnyBitPattern", "assert_any_bit_pattern")
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `CheckedBitPattern` for a fieldless enum, so that it can be
+/// converted from bytes with `SafeWithBytes` whenever the bytes hold one of
+/// its discriminants.
+///
+/// ```rust
+/// use as_with_bytes::{DecodeError, SafeWithBytes};
+/// use as_with_bytes_derive::CheckedBitPattern;
+///
+/// #[derive(CheckedBitPattern, Clone, Copy, Debug, PartialEq)]
+/// #[repr(u8)]
+/// enum Kind {
+///     File = 1,
+///     Directory = 2,
+/// }
+///
+/// assert_eq!(Kind::safe_with_bytes(&[2]), Ok(&Kind::Directory));
+/// assert_eq!(Kind::safe_with_bytes(&[3]), Err(DecodeError::InvalidBitPattern));
+/// ```
+///
+/// Enums without an integer representation are rejected:
+///
+/// ```compile_fail
+/// use as_with_bytes_derive::CheckedBitPattern;
+///
+/// #[derive(CheckedBitPattern, Clone, Copy)]
+/// enum Kind {
+///     File,
+///     Directory,
+/// }
+/// ```
+///
+/// So are enums whose layout is changed by `align` or `packed`:
+///
+/// ```compile_fail
+/// use as_with_bytes_derive::CheckedBitPattern;
+///
+/// #[derive(CheckedBitPattern, Clone, Copy)]
+/// #[repr(u8, align(4))]
+/// enum Kind {
+///     File,
+///     Directory,
+/// }
+/// ```
+#[proc_macro_derive(CheckedBitPattern)]
+pub fn derive_checked_bit_pattern(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_enum(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Generates the implementation of `CheckedBitPattern` for an enum.
+fn expand_enum(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let variants = match input.data {
+        Data::Enum(ref data) => &data.variants,
+        _ => return Err(Error::new_spanned(
+            &input.ident,
+            "only enums can derive `CheckedBitPattern`",
+        )),
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "generic enums cannot derive `CheckedBitPattern`",
+        ));
+    }
+    for variant in variants {
+        if !variant.fields.is_empty() {
+            return Err(Error::new_spanned(
+                variant,
+                "only fieldless enums can derive `CheckedBitPattern`",
+            ));
+        }
+    }
+    let repr = int_repr(input)?;
+
+    let name = &input.ident;
+    let idents = variants.iter().map(|variant| &variant.ident);
+    Ok(quote! {
+        const _: () = assert!(
+            ::as_with_bytes::__private::size_of::<#name>()
+                == ::as_with_bytes::__private::size_of::<#repr>()
+                && ::as_with_bytes::__private::align_of::<#name>()
+                    == ::as_with_bytes::__private::align_of::<#repr>(),
+            concat!("`", stringify!(#name), "` does not have the layout of its `#[repr]`"),
+        );
+
+        unsafe impl ::as_with_bytes::CheckedBitPattern for #name {
+            type Bits = #repr;
+
+            #[inline]
+            fn is_valid_bit_pattern(bits: &#repr) -> bool {
+                false #(|| *bits == #name::#idents as #repr)*
+            }
+        }
+    })
+}
+
+/// Finds the integer type in the `#[repr]` of an enum, rejecting modifiers
+/// such as `align(N)` which would give the enum a different layout.
+fn int_repr(input: &DeriveInput) -> Result<Ident, Error> {
+    const INTS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize",
+        "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    let mut found = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("repr") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("align") || meta.path.is_ident("packed") {
+                return Err(meta.error(
+                    "`CheckedBitPattern` cannot be derived for enums with `align` or `packed` modifiers",
+                ));
+            }
+            if let Some(ident) = meta.path.get_ident() {
+                if INTS.iter().any(|int| ident == int) {
+                    found = Some(ident.clone());
+                }
+            }
+            if meta.input.peek(syn::token::Paren) {
+                let content;
+                parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+            Ok(())
+        })?;
+    }
+    found.ok_or_else(|| Error::new_spanned(
+        &input.ident,
+        "`CheckedBitPattern` can only be derived for enums with an integer `#[repr]`",
+    ))
+}
+
+/// Generates the checks and the implementation of `marker` for a struct.
+fn expand(
+    input: &DeriveInput,
+    derive: &str,
+    marker: &str,
+    assert_fn: &str,
+) -> Result<TokenStream2, Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => return Err(Error::new_spanned(
+            &input.ident,
+            format!("only structs can derive `{}`", derive),
+        )),
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            format!("generic structs cannot derive `{}`", derive),
+        ));
+    }
+    check_repr(input, derive)?;
+
+    let name = &input.ident;
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let marker = Ident::new(marker, Span::call_site());
+    let assert_fn = Ident::new(assert_fn, Span::call_site());
+    Ok(quote! {
+        const _: () = {
+            #[allow(dead_code)]
+            fn assert_fields() {
+                #(::as_with_bytes::__private::#assert_fn::<#types>();)*
+            }
+
+            assert!(
+                ::as_with_bytes::__private::size_of::<#name>()
+                    == 0 #(+ ::as_with_bytes::__private::size_of::<#types>())*,
+                concat!("`", stringify!(#name), "` contains padding"),
+            );
+        };
+
+        unsafe impl ::as_with_bytes::#marker for #name {}
+    })
+}
+
+/// Checks that the struct is `#[repr(C)]` or `#[repr(transparent)]`.
+fn check_repr(input: &DeriveInput, derive: &str) -> Result<(), Error> {
+    let mut found = false;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("repr") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") || meta.path.is_ident("transparent") {
+                found = true;
+            } else if meta.input.peek(syn::token::Paren) {
+                // Skip the arguments of modifiers such as `align(8)`.
+                let content;
+                parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+            Ok(())
+        })?;
+    }
+    if found {
+        Ok(())
+    } else {
+        Err(Error::new_spanned(
+            &input.ident,
+            format!("`{}` can only be derived for `#[repr(C)]` or `#[repr(transparent)]` structs", derive),
+        ))
+    }
+}
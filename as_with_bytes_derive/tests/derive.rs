@@ -0,0 +1,66 @@
+#[macro_use]
+extern crate as_with_bytes_derive;
+
+use as_with_bytes::{AsBytes, DecodeError, SafeWithBytes};
+
+#[derive(AsBytes, WithBytes, Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Header {
+    kind: u16,
+    flags: u16,
+    len: u32,
+}
+
+#[derive(AsBytes, WithBytes, Clone, Copy, Debug, PartialEq)]
+#[repr(transparent)]
+struct Id(u64);
+
+#[derive(AsBytes, WithBytes, Clone, Copy, Debug, PartialEq)]
+#[repr(C, align(4))]
+struct Nested {
+    header: Header,
+    tag: [u8; 4],
+}
+
+#[derive(CheckedBitPattern, Clone, Copy, Debug, PartialEq)]
+#[repr(u16)]
+enum Opcode {
+    Nop,
+    Load = 10,
+    Store,
+}
+
+#[test]
+fn round_trip() {
+    let header = Header { kind: 1, flags: 2, len: 3 };
+
+    assert_eq!(Header::safe_with_bytes(header.as_bytes()), Ok(&header));
+    assert_eq!(Id::safe_with_bytes(Id(7).as_bytes()), Ok(&Id(7)));
+}
+
+#[test]
+fn nested_structs() {
+    let nested = Nested { header: Header { kind: 1, flags: 2, len: 3 }, tag: *b"abcd" };
+    let bytes = nested.as_bytes();
+
+    assert_eq!(&bytes[8..], b"abcd");
+    assert_eq!(
+        Nested::safe_with_bytes(&bytes[..8]),
+        Err(DecodeError::TooShort { expected: 12, actual: 8 }),
+    );
+}
+
+#[test]
+fn enums() {
+    let codes = [0u16, 10, 11];
+
+    assert_eq!(
+        <[Opcode]>::safe_with_bytes(codes.as_bytes()),
+        Ok(&[Opcode::Nop, Opcode::Load, Opcode::Store][..]),
+    );
+    assert_eq!(Opcode::safe_with_bytes(1u16.as_bytes()), Err(DecodeError::InvalidBitPattern));
+    assert_eq!(
+        Opcode::safe_with_bytes(&codes.as_bytes()[..1]),
+        Err(DecodeError::TooShort { expected: 2, actual: 1 }),
+    );
+}
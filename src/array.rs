@@ -0,0 +1,108 @@
+use core::marker::PhantomData;
+use core::mem;
+
+use {read_from, AnyBitPattern, AsBytes, NoUninit};
+
+/// Compile-time check that a `T` takes up exactly `N` bytes.
+struct SizeIs<T, const N: usize>(PhantomData<T>);
+
+impl <T, const N: usize> SizeIs<T, N> {
+    const OK: () = assert!(
+        mem::size_of::<T>() == N,
+        "the length of the byte array does not match the size of the type",
+    );
+}
+
+/// A trait for copying a value into an array of exactly as many bytes as it
+/// takes up.
+///
+/// This is implemented for every `T: NoUninit` with `N` equal to
+/// `size_of::<T>()`. Using any other `N` is an error when the crate is
+/// built, but not when it is only checked with `cargo check`, and not in
+/// generic code which is never used with a concrete `T`.
+///
+/// ```rust
+/// use as_with_bytes::IntoByteArray;
+///
+/// let bytes: [u8; 4] = 0x0102_0304u32.to_byte_array();
+/// assert_eq!(bytes, 0x0102_0304u32.to_ne_bytes());
+/// ```
+///
+/// ```compile_fail
+/// use as_with_bytes::IntoByteArray;
+///
+/// let bytes: [u8; 8] = 0u32.to_byte_array();
+/// ```
+pub trait IntoByteArray<const N: usize> {
+    /// Returns a copy of the bytes of `self`.
+    fn to_byte_array(&self) -> [u8; N];
+}
+
+/// A trait for copying a value out of an array of exactly as many bytes as
+/// it takes up.
+///
+/// This is implemented for every `T: AnyBitPattern` with `N` equal to
+/// `size_of::<T>()`. As with `IntoByteArray`, using any other `N` is an
+/// error when the crate is built, though `cargo check` does not catch it.
+/// The array can have any alignment, since the value is copied out of it.
+///
+/// ```rust
+/// use as_with_bytes::FromByteArray;
+///
+/// assert_eq!(u16::from_byte_array(7u16.to_ne_bytes()), 7);
+/// ```
+///
+/// ```compile_fail
+/// use as_with_bytes::FromByteArray;
+///
+/// let n = u16::from_byte_array([0u8; 3]);
+/// ```
+pub trait FromByteArray<const N: usize>: Sized {
+    /// Returns the value whose bytes are `bytes`.
+    fn from_byte_array(bytes: [u8; N]) -> Self;
+}
+
+impl <T: NoUninit, const N: usize> IntoByteArray<N> for T {
+    #[inline]
+    fn to_byte_array(&self) -> [u8; N] {
+        let () = SizeIs::<T, N>::OK;
+        let mut array = [0; N];
+        array.copy_from_slice(self.as_bytes());
+        array
+    }
+}
+
+impl <T: AnyBitPattern, const N: usize> FromByteArray<N> for T {
+    #[inline]
+    fn from_byte_array(bytes: [u8; N]) -> T {
+        let () = SizeIs::<T, N>::OK;
+        read_from(&bytes).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use endian::U32Be;
+
+    #[test]
+    fn round_trip() {
+        let pair = [1u16, 2];
+        let bytes: [u8; 4] = pair.to_byte_array();
+
+        assert_eq!(bytes[..], *pair.as_bytes());
+        assert_eq!(<[u16; 2]>::from_byte_array(bytes), pair);
+        assert_eq!(U32Be::new(5).to_byte_array(), [0, 0, 0, 5]);
+        assert_eq!(U32Be::from_byte_array([0, 0, 1, 0]).get(), 256);
+    }
+
+    #[test]
+    fn store_in_field() {
+        struct Record {
+            stamp: [u8; 8],
+        }
+
+        let record = Record { stamp: 12345u64.to_byte_array() };
+        assert_eq!(u64::from_byte_array(record.stamp), 12345);
+    }
+}
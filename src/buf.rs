@@ -0,0 +1,272 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::slice;
+
+use {AnyBitPattern, DecodeError, NoUninit, SafeWithBytes};
+
+macro_rules! align_types {
+    ($($name:ident($align:literal);)*) => {
+        $(
+            #[doc = concat!("A block of ", stringify!($align), " bytes aligned to ", stringify!($align), " bytes.")]
+            ///
+            /// This is meant to be used as the `A` parameter of `AlignedBuf`.
+            #[derive(Clone, Copy)]
+            #[repr(C, align($align))]
+            pub struct $name([u8; $align]);
+
+            unsafe impl AnyBitPattern for $name {}
+
+            unsafe impl NoUninit for $name {}
+        )*
+    };
+}
+
+align_types! {
+    Align2(2);
+    Align4(4);
+    Align8(8);
+    Align16(16);
+    Align32(32);
+    Align64(64);
+}
+
+/// A growable buffer of bytes which starts at an address aligned like `A`.
+///
+/// A `Vec<u8>` only guarantees an alignment of 1, so decoding values from it
+/// is unreliable. An `AlignedBuf` stores its bytes in a `Vec<A>` instead, so
+/// values with an alignment up to that of `A` can be viewed in it as long as
+/// they start at a suitable offset. The `view` and `view_slice` methods check
+/// this.
+///
+/// `A` is normally one of the `Align*` types, but any non-zero-sized type
+/// implementing `AnyBitPattern` and `NoUninit` works.
+pub struct AlignedBuf<A: AnyBitPattern + NoUninit = Align8> {
+    storage: Vec<A>,
+    len: usize,
+}
+
+impl <A: AnyBitPattern + NoUninit> AlignedBuf<A> {
+    /// Creates an empty buffer.
+    ///
+    /// # Panics
+    /// This function panics if `A` has a size of zero.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty buffer with room for at least `capacity` bytes.
+    ///
+    /// # Panics
+    /// This function panics if `A` has a size of zero.
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert!(mem::size_of::<A>() != 0, "AlignedBuf cannot store bytes in a zero-sized type");
+        AlignedBuf {
+            storage: Vec::with_capacity(Self::units_for(capacity)),
+            len: 0,
+        }
+    }
+
+    /// Creates a buffer of `len` zero bytes, ready to be filled in.
+    ///
+    /// # Panics
+    /// This function panics if `A` has a size of zero.
+    pub fn zeroed(len: usize) -> Self {
+        let mut buf = Self::with_capacity(len);
+        buf.resize(len, 0);
+        buf
+    }
+
+    /// Returns the number of bytes in the buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the buffer contains no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bytes the buffer can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.storage.capacity() * mem::size_of::<A>()
+    }
+
+    /// Reserves room for at least `additional` more bytes.
+    pub fn reserve(&mut self, additional: usize) {
+        let units = Self::units_for(self.len.checked_add(additional).expect("capacity overflow"));
+        self.storage.reserve(units.saturating_sub(self.storage.len()));
+    }
+
+    /// Resizes the buffer to `new_len` bytes, filling any new bytes with
+    /// `value`.
+    pub fn resize(&mut self, new_len: usize, value: u8) {
+        let old_len = self.len;
+        self.set_len(new_len);
+        if new_len > old_len {
+            for byte in &mut self[old_len..] {
+                *byte = value;
+            }
+        }
+    }
+
+    /// Shortens the buffer to `len` bytes. This has no effect if the buffer
+    /// is already shorter than that.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.set_len(len);
+        }
+    }
+
+    /// Removes all the bytes from the buffer.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Appends a byte to the end of the buffer.
+    #[inline]
+    pub fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    /// Appends bytes to the end of the buffer.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let old_len = self.len;
+        self.set_len(old_len.checked_add(bytes.len()).expect("capacity overflow"));
+        self[old_len..].copy_from_slice(bytes);
+    }
+
+    /// Returns the bytes in the buffer.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.storage.as_ptr() as *const u8, self.len) }
+    }
+
+    /// Returns the bytes in the buffer mutably.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.storage.as_mut_ptr() as *mut u8, self.len) }
+    }
+
+    /// Decodes a `T` from the start of the buffer.
+    #[inline]
+    pub fn view<T: AnyBitPattern>(&self) -> Result<&T, DecodeError> {
+        T::safe_with_bytes(self)
+    }
+
+    /// Decodes as many `T`s as fit in the buffer.
+    #[inline]
+    pub fn view_slice<T: AnyBitPattern>(&self) -> Result<&[T], DecodeError> {
+        <[T]>::safe_with_bytes(self)
+    }
+
+    /// Returns the number of `A`s needed to hold `len` bytes.
+    #[inline]
+    fn units_for(len: usize) -> usize {
+        len.div_ceil(mem::size_of::<A>())
+    }
+
+    /// Sets the length of the buffer, zeroing any newly allocated storage.
+    fn set_len(&mut self, len: usize) {
+        // Every bit pattern is valid for `A`, so zeroed memory is as well.
+        let zero: A = unsafe { mem::zeroed() };
+        self.storage.resize(Self::units_for(len), zero);
+        self.len = len;
+    }
+}
+
+impl <A: AnyBitPattern + NoUninit> Default for AlignedBuf<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <A: AnyBitPattern + NoUninit> Clone for AlignedBuf<A> {
+    fn clone(&self) -> Self {
+        AlignedBuf {
+            storage: self.storage.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl <A: AnyBitPattern + NoUninit> Deref for AlignedBuf<A> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl <A: AnyBitPattern + NoUninit> DerefMut for AlignedBuf<A> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl <A: AnyBitPattern + NoUninit> fmt::Debug for AlignedBuf<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl <'a, A: AnyBitPattern + NoUninit> From<&'a [u8]> for AlignedBuf<A> {
+    fn from(bytes: &'a [u8]) -> Self {
+        let mut buf = Self::with_capacity(bytes.len());
+        buf.extend_from_slice(bytes);
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AsBytes;
+
+    #[test]
+    fn storage_is_aligned() {
+        let mut buf = AlignedBuf::<Align16>::new();
+
+        for i in 0..100 {
+            buf.push(i);
+            assert_eq!(buf.as_ptr() as usize % 16, 0);
+        }
+        assert_eq!(buf.len(), 100);
+        assert_eq!(buf[99], 99);
+    }
+
+    #[test]
+    fn views() {
+        let buf = AlignedBuf::<Align8>::from([1u64, 2, 3].as_bytes());
+
+        assert_eq!(buf.view::<u64>(), Ok(&1));
+        assert_eq!(buf.view_slice::<u32>().map(<[u32]>::len), Ok(6));
+        assert_eq!(
+            AlignedBuf::<Align2>::zeroed(6).view::<u64>(),
+            Err(DecodeError::TooShort { expected: 8, actual: 6 }),
+        );
+    }
+
+    #[test]
+    fn resize_and_truncate() {
+        let mut buf = AlignedBuf::<Align4>::zeroed(3);
+
+        buf.resize(6, 7);
+        assert_eq!(&buf[..], &[0, 0, 0, 7, 7, 7]);
+        buf.truncate(2);
+        buf.resize(5, 1);
+        assert_eq!(&buf[..], &[0, 0, 1, 1, 1]);
+        buf.clear();
+        assert!(buf.is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+use core::marker::PhantomData;
+use core::mem;
+use core::slice;
+
+use {AnyBitPattern, AsBytes, AsBytesMut, CastError, CheckedBitPattern, DecodeError, NoUninit};
+use {SafeWithBytes, SafeWithBytesMut, WithBytes, WithBytesMut};
+
+/// Compile-time checks that an `A` can be reinterpreted as a `B`.
+struct Layout<A, B>(PhantomData<(A, B)>);
+
+impl <A, B> Layout<A, B> {
+    const SAME_SIZE: () = assert!(
+        mem::size_of::<A>() == mem::size_of::<B>(),
+        "cannot cast between types of different sizes",
+    );
+
+    const ALIGNED: () = assert!(
+        mem::align_of::<A>() >= mem::align_of::<B>(),
+        "cannot cast to a type with a stricter alignment",
+    );
+}
+
+/// Reinterprets the bytes of `a` as a `B`.
+///
+/// `A` and `B` must have the same size, and `B` must not be more strictly
+/// aligned than `A`. Both are checked when the crate is built, so casting
+/// `&[u32; 4]` to `&[u8; 16]` works, but casting `&[u8; 16]` to `&[u32; 4]`
+/// fails to build. `cargo check` does not catch this, and neither does
+/// generic code which is never used with concrete types. Use `try_cast_ref`
+/// to check the alignment of the address at run time instead.
+///
+/// ```rust
+/// use as_with_bytes::cast_ref;
+///
+/// let n = 0x0102_0304u32;
+/// let bytes: &[u8; 4] = cast_ref(&n);
+/// assert_eq!(*bytes, n.to_ne_bytes());
+/// ```
+///
+/// ```compile_fail
+/// use as_with_bytes::cast_ref;
+///
+/// let n: &u64 = cast_ref(&[0u8; 8]);
+/// ```
+#[inline]
+pub fn cast_ref<A: NoUninit, B: AnyBitPattern>(a: &A) -> &B {
+    let () = Layout::<A, B>::SAME_SIZE;
+    let () = Layout::<A, B>::ALIGNED;
+    unsafe { B::with_bytes(a.as_bytes()) }
+}
+
+/// Reinterprets the bytes of `a` as a mutable `B`.
+///
+/// The same requirements as for `cast_ref` are checked when the crate is
+/// built.
+/// Both types must also be valid for any bit pattern and contain no
+/// padding, since writing through either one changes the other.
+#[inline]
+pub fn cast_mut<A, B>(a: &mut A) -> &mut B
+where
+    A: NoUninit + AnyBitPattern,
+    B: NoUninit + AnyBitPattern,
+{
+    let () = Layout::<A, B>::SAME_SIZE;
+    let () = Layout::<A, B>::ALIGNED;
+    unsafe { B::with_bytes_mut(a.as_bytes_mut()) }
+}
+
+/// Reinterprets the bytes of `a` as a `B`, checking at run time that the
+/// sizes match, that `a` is aligned for `B`, and that the bytes make up a
+/// valid `B`.
+#[inline]
+pub fn try_cast_ref<A: NoUninit, B: CheckedBitPattern>(a: &A) -> Result<&B, CastError> {
+    B::safe_with_bytes_exact(a.as_bytes()).map_err(cast_error)
+}
+
+/// Reinterprets the bytes of the elements of `a` as a slice of `B`.
+///
+/// Unlike `<[B]>::with_bytes`, this fails instead of dropping bytes which do
+/// not make up a whole `B`, and checks that `a` is aligned for `B` and that
+/// every element is a valid `B`. Fails with `CastError::ZeroSized` if `B`
+/// has a size of zero.
+///
+/// ```rust
+/// use as_with_bytes::{cast_slice, CastError};
+///
+/// let words = [1u32, 2];
+/// let halves: &[u16] = cast_slice(&words).unwrap();
+/// assert_eq!(halves.len(), 4);
+/// assert_eq!(
+///     cast_slice::<u32, [u8; 3]>(&words),
+///     Err(CastError::SizeMismatch { expected: 6, actual: 8 }),
+/// );
+/// ```
+#[inline]
+pub fn cast_slice<A: NoUninit, B: CheckedBitPattern>(a: &[A]) -> Result<&[B], CastError> {
+    <[B]>::safe_with_bytes_exact(a.as_bytes()).map_err(cast_error)
+}
+
+/// Reinterprets the bytes of the elements of `a` as a mutable slice of `B`.
+///
+/// This fails under the same conditions as `cast_slice`. Both types must be
+/// valid for any bit pattern and contain no padding, since writing through
+/// either one changes the other.
+#[inline]
+pub fn cast_slice_mut<A, B>(a: &mut [A]) -> Result<&mut [B], CastError>
+where
+    A: NoUninit + AnyBitPattern,
+    B: NoUninit + AnyBitPattern,
+{
+    <[B]>::safe_with_bytes_mut_exact(a.as_bytes_mut()).map_err(cast_error)
+}
+
+/// Splits the bytes of the elements of `a` into the bytes before the first
+/// address aligned for `B`, as many `B`s as fit after that, and the bytes
+/// left over at the end.
+///
+/// No bytes are lost, so joining the three parts gives back all of the bytes
+/// of `a`. If `B` has a size of zero, every byte ends up in the first part.
+///
+/// ```rust
+/// use as_with_bytes::{split_cast_slice, AsBytes};
+///
+/// let words = [0u32; 3];
+/// let (head, middle, tail) = split_cast_slice::<u8, u32>(&words.as_bytes()[1..11]);
+/// assert_eq!((head.len(), middle.len(), tail.len()), (3, 1, 3));
+/// ```
+pub fn split_cast_slice<A: NoUninit, B: AnyBitPattern>(a: &[A]) -> (&[u8], &[B], &[u8]) {
+    let bytes = a.as_bytes();
+    let size = mem::size_of::<B>();
+    if size == 0 {
+        return (bytes, &[], &[]);
+    }
+    let head_len = bytes.as_ptr().align_offset(mem::align_of::<B>());
+    if head_len >= bytes.len() {
+        return (bytes, &[], &[]);
+    }
+    let (head, rest) = bytes.split_at(head_len);
+    let (middle, tail) = rest.split_at(rest.len() - rest.len() % size);
+    // `middle` starts at an aligned address and holds a whole number of `B`s.
+    let middle = unsafe {
+        slice::from_raw_parts(middle.as_ptr() as *const B, middle.len() / size)
+    };
+    (head, middle, tail)
+}
+
+/// Translates the reason why bytes could not be decoded into the reason why
+/// a cast failed.
+pub(crate) fn cast_error(err: DecodeError) -> CastError {
+    match err {
+        DecodeError::TooShort { expected, actual }
+        | DecodeError::TrailingBytes { expected, actual } => {
+            CastError::SizeMismatch { expected, actual }
+        }
+        DecodeError::Misaligned { align, offset } => CastError::Misaligned { align, offset },
+        DecodeError::ZeroSized => CastError::ZeroSized,
+        _ => CastError::InvalidBitPattern,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroU16;
+    use super::*;
+
+    #[test]
+    fn static_casts() {
+        let words = [1u32, 2, 3, 4];
+        let bytes: &[u8; 16] = cast_ref(&words);
+        assert_eq!(bytes[..], *words.as_bytes());
+
+        let mut n = 0u64;
+        cast_mut::<_, [u16; 4]>(&mut n)[3] = 0xffff;
+        assert_eq!(n.as_bytes()[..6], [0; 6]);
+        assert_eq!(n.as_bytes()[6..], [0xff; 2]);
+    }
+
+    #[test]
+    fn checked_casts() {
+        let pair = [0u16, 7];
+        let words = [0u32; 2];
+        let bytes: &[u8; 8] = cast_ref(&words);
+        let middle = <[u8; 4]>::safe_with_bytes(&bytes[2..6]).unwrap();
+
+        assert_eq!(try_cast_ref::<_, [u8; 4]>(&pair), Ok(cast_ref(&pair)));
+        assert_eq!(
+            try_cast_ref::<_, u64>(&pair),
+            Err(CastError::SizeMismatch { expected: 8, actual: 4 }),
+        );
+        assert_eq!(
+            try_cast_ref::<_, u32>(middle),
+            Err(CastError::Misaligned { align: 4, offset: 2 }),
+        );
+        assert_eq!(try_cast_ref::<_, NonZeroU16>(&pair[1]), Ok(&NonZeroU16::new(7).unwrap()));
+        assert_eq!(try_cast_ref::<_, NonZeroU16>(&pair[0]), Err(CastError::InvalidBitPattern));
+        assert_eq!(try_cast_ref::<_, bool>(&2u8), Err(CastError::InvalidBitPattern));
+    }
+
+    #[test]
+    fn slice_casts() {
+        let mut words = [0u32, 1];
+        let bytes = <[u8]>::safe_with_bytes(words.as_bytes()).unwrap();
+
+        assert_eq!(cast_slice::<u32, u16>(&words).map(<[u16]>::len), Ok(4));
+        assert_eq!(
+            cast_slice::<u8, u32>(&bytes[2..6]),
+            Err(CastError::Misaligned { align: 4, offset: 2 }),
+        );
+        assert_eq!(
+            cast_slice::<u8, u32>(&bytes[..6]),
+            Err(CastError::SizeMismatch { expected: 4, actual: 6 }),
+        );
+        assert_eq!(cast_slice::<u8, ()>(bytes), Err(CastError::ZeroSized));
+        assert_eq!(cast_slice::<u32, bool>(&words[..1]).map(<[bool]>::len), Ok(4));
+        assert_eq!(cast_slice::<u32, bool>(&[2]), Err(CastError::InvalidBitPattern));
+
+        cast_slice_mut::<u32, u8>(&mut words).unwrap()[4..].copy_from_slice(&7u32.to_ne_bytes());
+        assert_eq!(words, [0, 7]);
+    }
+
+    #[test]
+    fn split_casts() {
+        let words = [0u32, 1, 2];
+        let bytes = words.as_bytes();
+
+        let (head, middle, tail) = split_cast_slice::<u8, u32>(&bytes[2..]);
+        assert_eq!((head, middle, tail), (&bytes[2..4], &words[1..], &[][..]));
+
+        let (head, middle, tail) = split_cast_slice::<u8, [u16; 3]>(&bytes[..11]);
+        assert_eq!((head.len(), middle.len(), tail), (0, 1, &bytes[6..11]));
+
+        let (head, middle, tail) = split_cast_slice::<u8, u32>(&bytes[1..4]);
+        assert_eq!((head, middle, tail), (&bytes[1..4], &[][..], &[][..]));
+
+        let (head, middle, tail) = split_cast_slice::<u8, u32>(&bytes[1..3]);
+        assert_eq!((head, middle, tail), (&bytes[1..3], &[][..], &[][..]));
+    }
+}
@@ -0,0 +1,65 @@
+use core::mem;
+use core::ptr;
+
+use {AnyBitPattern, DecodeError};
+
+/// Copies a `T` out of the start of `bytes`, or returns `None` if there are
+/// not enough bytes.
+///
+/// Unlike `try_with_bytes`, this works no matter how `bytes` is aligned, at
+/// the cost of copying the value.
+#[inline]
+pub fn read_from<T: AnyBitPattern>(bytes: &[u8]) -> Option<T> {
+    if bytes.len() < mem::size_of::<T>() {
+        None
+    } else {
+        Some(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const T) })
+    }
+}
+
+/// Copies values out of the start of `bytes` to fill `dest`, or fails
+/// with `DecodeError::TooShort` without changing `dest` if there are not
+/// enough bytes.
+///
+/// Like `read_from`, this works no matter how `bytes` is aligned.
+#[inline]
+pub fn read_slice_into<T: AnyBitPattern>(dest: &mut [T], bytes: &[u8]) -> Result<(), DecodeError> {
+    let size = mem::size_of_val(dest);
+    if bytes.len() < size {
+        return Err(DecodeError::TooShort { expected: size, actual: bytes.len() });
+    }
+    unsafe {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), dest.as_mut_ptr() as *mut u8, size);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AsBytes;
+
+    #[test]
+    fn read_misaligned() {
+        let arr = [0u8, 1, 2, 3, 4, 5, 6, 7, 8];
+
+        assert_eq!(read_from::<u64>(&arr[1..]), Some(u64::from_ne_bytes([1, 2, 3, 4, 5, 6, 7, 8])));
+        assert_eq!(read_from::<u64>(&arr[2..]), None);
+    }
+
+    #[test]
+    fn read_slices() {
+        let src = [1u16, 2, 3];
+        let mut bytes = [0u8; 7];
+        let mut dest = [0u16; 3];
+
+        bytes[1..].copy_from_slice(src.as_bytes());
+        assert_eq!(read_slice_into(&mut dest, &bytes[1..]), Ok(()));
+        assert_eq!(dest, src);
+        assert_eq!(
+            read_slice_into(&mut dest, &bytes[2..]),
+            Err(DecodeError::TooShort { expected: 6, actual: 5 }),
+        );
+        assert_eq!(dest, src);
+    }
+}
@@ -0,0 +1,150 @@
+//! Numbers stored with a fixed byte order.
+//!
+//! Each type here wraps an array of bytes, so it has an alignment of 1 and
+//! the same layout on every system. This makes them suitable as fields of
+//! structs which are shared between systems with different byte orders.
+
+use core::fmt;
+
+use {AnyBitPattern, NoUninit};
+
+macro_rules! endian_types {
+    ($($name:ident($native:ident, $size:expr, $order:literal, $to_bytes:ident, $from_bytes:ident);)*) => {
+        $(
+            #[doc = concat!("A `", stringify!($native), "` stored in ", $order, " byte order.")]
+            #[derive(Clone, Copy, Default)]
+            #[repr(transparent)]
+            pub struct $name([u8; $size]);
+
+            impl $name {
+                #[doc = concat!("Stores `value` in ", $order, " byte order.")]
+                #[inline]
+                pub fn new(value: $native) -> Self {
+                    $name(value.$to_bytes())
+                }
+
+                /// Returns the stored value in native byte order.
+                #[inline]
+                pub fn get(self) -> $native {
+                    $native::$from_bytes(self.0)
+                }
+
+                /// Replaces the stored value.
+                #[inline]
+                pub fn set(&mut self, value: $native) {
+                    self.0 = value.$to_bytes();
+                }
+            }
+
+            impl From<$native> for $name {
+                #[inline]
+                fn from(value: $native) -> Self {
+                    $name::new(value)
+                }
+            }
+
+            impl From<$name> for $native {
+                #[inline]
+                fn from(value: $name) -> Self {
+                    value.get()
+                }
+            }
+
+            impl fmt::Debug for $name {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.debug_tuple(stringify!($name)).field(&self.get()).finish()
+                }
+            }
+
+            unsafe impl AnyBitPattern for $name {}
+
+            unsafe impl NoUninit for $name {}
+        )*
+    };
+}
+
+macro_rules! endian_integers {
+    ($($name:ident($native:ident, $size:expr, $order:literal, $to_bytes:ident, $from_bytes:ident);)*) => {
+        endian_types! {
+            $($name($native, $size, $order, $to_bytes, $from_bytes);)*
+        }
+
+        $(
+            impl PartialEq for $name {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    self.0 == other.0
+                }
+            }
+
+            impl Eq for $name {}
+
+            impl core::hash::Hash for $name {
+                #[inline]
+                fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                    self.get().hash(state)
+                }
+            }
+        )*
+    };
+}
+
+endian_integers! {
+    U16Le(u16, 2, "little-endian", to_le_bytes, from_le_bytes);
+    U16Be(u16, 2, "big-endian", to_be_bytes, from_be_bytes);
+    U32Le(u32, 4, "little-endian", to_le_bytes, from_le_bytes);
+    U32Be(u32, 4, "big-endian", to_be_bytes, from_be_bytes);
+    U64Le(u64, 8, "little-endian", to_le_bytes, from_le_bytes);
+    U64Be(u64, 8, "big-endian", to_be_bytes, from_be_bytes);
+    U128Le(u128, 16, "little-endian", to_le_bytes, from_le_bytes);
+    U128Be(u128, 16, "big-endian", to_be_bytes, from_be_bytes);
+    I16Le(i16, 2, "little-endian", to_le_bytes, from_le_bytes);
+    I16Be(i16, 2, "big-endian", to_be_bytes, from_be_bytes);
+    I32Le(i32, 4, "little-endian", to_le_bytes, from_le_bytes);
+    I32Be(i32, 4, "big-endian", to_be_bytes, from_be_bytes);
+    I64Le(i64, 8, "little-endian", to_le_bytes, from_le_bytes);
+    I64Be(i64, 8, "big-endian", to_be_bytes, from_be_bytes);
+    I128Le(i128, 16, "little-endian", to_le_bytes, from_le_bytes);
+    I128Be(i128, 16, "big-endian", to_be_bytes, from_be_bytes);
+}
+
+// Floats are not compared byte by byte, since that would disagree with the
+// way `f32` and `f64` compare NaNs and zeros.
+endian_types! {
+    F32Le(f32, 4, "little-endian", to_le_bytes, from_le_bytes);
+    F32Be(f32, 4, "big-endian", to_be_bytes, from_be_bytes);
+    F64Le(f64, 8, "little-endian", to_le_bytes, from_le_bytes);
+    F64Be(f64, 8, "big-endian", to_be_bytes, from_be_bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {AsBytes, SafeWithBytes};
+
+    #[test]
+    fn byte_order_is_fixed() {
+        assert_eq!(U32Le::new(0x0102_0304).as_bytes(), &[4, 3, 2, 1]);
+        assert_eq!(U32Be::new(0x0102_0304).as_bytes(), &[1, 2, 3, 4]);
+        assert_eq!(I16Be::new(-2).as_bytes(), &[0xff, 0xfe]);
+        assert_eq!(F32Be::new(1.0).as_bytes(), &[0x3f, 0x80, 0, 0]);
+    }
+
+    #[test]
+    fn decode_at_any_offset() {
+        let bytes = [0, 0, 0, 0x12, 0x34, 0, 0, 0];
+
+        assert_eq!(U16Be::safe_with_bytes(&bytes[3..]).map(|n| n.get()), Ok(0x1234));
+        assert_eq!(U16Le::safe_with_bytes(&bytes[3..]).map(|n| n.get()), Ok(0x3412));
+    }
+
+    #[test]
+    fn get_and_set() {
+        let mut n = U64Le::from(5);
+
+        n.set(n.get() + 1);
+        assert_eq!(u64::from(n), 6);
+        assert_eq!(n, U64Le::new(6));
+        assert_eq!(F64Le::new(0.5).get(), 0.5);
+    }
+}
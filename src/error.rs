@@ -0,0 +1,194 @@
+use core::fmt;
+
+/// The reason why a slice of bytes could not be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// There were fewer bytes than the decoded type needs.
+    TooShort {
+        /// The number of bytes needed.
+        expected: usize,
+        /// The number of bytes given.
+        actual: usize,
+    },
+    /// The bytes did not start at an address which is a multiple of the
+    /// alignment of the decoded type.
+    Misaligned {
+        /// The alignment of the decoded type.
+        align: usize,
+        /// How far the bytes are past the previous aligned address.
+        offset: usize,
+    },
+    /// There were bytes left over after the decoded value.
+    TrailingBytes {
+        /// The number of bytes the decoded value takes up.
+        expected: usize,
+        /// The number of bytes given.
+        actual: usize,
+    },
+    /// A slice of a type with a size of zero was requested, so the number
+    /// of elements could not be determined from the length of the bytes.
+    /// `with_bytes_n` can decode such slices given the number of elements.
+    ZeroSized,
+    /// A length prefix held a number too large for a `usize`.
+    LengthOverflow,
+    /// The bytes of a string were not valid UTF-8.
+    InvalidUtf8 {
+        /// The number of bytes before the first invalid sequence.
+        valid_up_to: usize,
+    },
+    /// The bytes of a C string had no nul terminator.
+    MissingNul,
+    /// The bytes did not make up a valid value of the decoded type, such as
+    /// a `bool` other than 0 or 1.
+    InvalidBitPattern,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::TooShort { expected, actual } => write!(
+                f,
+                "not enough bytes: expected {}, found {}",
+                expected, actual,
+            ),
+            DecodeError::Misaligned { align, offset } => write!(
+                f,
+                "bytes are misaligned: {} bytes past a multiple of {}",
+                offset, align,
+            ),
+            DecodeError::TrailingBytes { expected, actual } => write!(
+                f,
+                "too many bytes: expected {}, found {}",
+                expected, actual,
+            ),
+            DecodeError::ZeroSized => f.write_str("cannot decode a slice of a zero-sized type"),
+            DecodeError::LengthOverflow => f.write_str("length prefix does not fit in a usize"),
+            DecodeError::InvalidUtf8 { valid_up_to } => write!(
+                f,
+                "invalid UTF-8 after {} bytes",
+                valid_up_to,
+            ),
+            DecodeError::MissingNul => f.write_str("no nul terminator found"),
+            DecodeError::InvalidBitPattern => f.write_str("bytes are not a valid value of the type"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// The reason why a value could not be written into a slice of bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WriteError {
+    /// The destination had fewer bytes than the value.
+    TooShort {
+        /// The number of bytes in the value.
+        expected: usize,
+        /// The number of bytes in the destination.
+        actual: usize,
+    },
+    /// The destination had more bytes than the value, and the whole
+    /// destination was supposed to be filled.
+    TooLong {
+        /// The number of bytes in the value.
+        expected: usize,
+        /// The number of bytes in the destination.
+        actual: usize,
+    },
+    /// The place in the destination where a value was to be put was not at
+    /// an address which is a multiple of the alignment of the value.
+    Misaligned {
+        /// The alignment of the value.
+        align: usize,
+        /// How far the place is past the previous aligned address.
+        offset: usize,
+    },
+    /// A slice was too long for the length prefix chosen for it.
+    LengthOverflow {
+        /// The number of elements in the slice.
+        len: usize,
+        /// The largest number the length prefix can hold.
+        max: usize,
+    },
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WriteError::TooShort { expected, actual } => write!(
+                f,
+                "destination too short: needed {} bytes, found {}",
+                expected, actual,
+            ),
+            WriteError::TooLong { expected, actual } => write!(
+                f,
+                "destination too long: needed {} bytes, found {}",
+                expected, actual,
+            ),
+            WriteError::Misaligned { align, offset } => write!(
+                f,
+                "destination is misaligned: {} bytes past a multiple of {}",
+                offset, align,
+            ),
+            WriteError::LengthOverflow { len, max } => write!(
+                f,
+                "slice too long for length prefix: {} elements, at most {} allowed",
+                len, max,
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WriteError {}
+
+/// The reason why a value could not be reinterpreted as another type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CastError {
+    /// The two types have different sizes.
+    SizeMismatch {
+        /// The number of bytes in the target type, or for slices, the
+        /// number of bytes which make up whole elements of it.
+        expected: usize,
+        /// The number of bytes in the value.
+        actual: usize,
+    },
+    /// The value was not at an address which is a multiple of the alignment
+    /// of the target type.
+    Misaligned {
+        /// The alignment of the target type.
+        align: usize,
+        /// How far the value is past the previous aligned address.
+        offset: usize,
+    },
+    /// The bytes of the value are not a valid value of the target type.
+    InvalidBitPattern,
+    /// A slice of a type with a size of zero was requested, so the number
+    /// of elements could not be determined.
+    ZeroSized,
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CastError::SizeMismatch { expected, actual } => write!(
+                f,
+                "size mismatch: expected {} bytes, found {}",
+                expected, actual,
+            ),
+            CastError::Misaligned { align, offset } => write!(
+                f,
+                "value is misaligned: {} bytes past a multiple of {}",
+                offset, align,
+            ),
+            CastError::InvalidBitPattern => f.write_str("bytes are not a valid value of the target type"),
+            CastError::ZeroSized => f.write_str("cannot cast to a slice of a zero-sized type"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CastError {}
@@ -0,0 +1,71 @@
+use core::mem::{self, MaybeUninit};
+use core::slice;
+use std::io;
+
+use {AnyBitPattern, AsBytes, AsBytesMut, NoUninit};
+
+/// Extends `io::Write` with methods for writing the bytes of values.
+pub trait WriteBytesExt: io::Write {
+    /// Writes the bytes of `value`.
+    #[inline]
+    fn write_value<T: NoUninit>(&mut self, value: &T) -> io::Result<()> {
+        self.write_all(value.as_bytes())
+    }
+
+    /// Writes the bytes of every value in `values`.
+    #[inline]
+    fn write_slice<T: NoUninit>(&mut self, values: &[T]) -> io::Result<()> {
+        self.write_all(values.as_bytes())
+    }
+}
+
+impl <W: io::Write + ?Sized> WriteBytesExt for W {}
+
+/// Extends `io::Read` with methods for reading values from bytes.
+pub trait ReadBytesExt: io::Read {
+    /// Reads exactly `size_of::<T>()` bytes and returns them as a `T`.
+    fn read_value<T: AnyBitPattern>(&mut self) -> io::Result<T> {
+        // The zeroed bytes are initialized, so they can be handed to `read`.
+        let mut value = MaybeUninit::<T>::zeroed();
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(value.as_mut_ptr() as *mut u8, mem::size_of::<T>())
+        };
+        self.read_exact(bytes)?;
+        Ok(unsafe { value.assume_init() })
+    }
+
+    /// Reads exactly enough bytes to fill `dest`.
+    #[inline]
+    fn read_exact_into<T: AnyBitPattern + NoUninit>(&mut self, dest: &mut [T]) -> io::Result<()> {
+        self.read_exact(dest.as_bytes_mut())
+    }
+}
+
+impl <R: io::Read + ?Sized> ReadBytesExt for R {}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+    use super::*;
+    use endian::U16Be;
+
+    #[test]
+    fn round_trip() {
+        let mut out = Vec::new();
+
+        out.write_value(&U16Be::new(2)).unwrap();
+        out.write_slice(&[10u32, 20]).unwrap();
+
+        let mut input = &out[..];
+        let len = input.read_value::<U16Be>().unwrap().get();
+        let mut items = [0u32; 2];
+
+        assert_eq!(len, 2);
+        input.read_exact_into(&mut items[..len as usize]).unwrap();
+        assert_eq!(items, [10, 20]);
+        assert_eq!(
+            input.read_value::<u8>().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof,
+        );
+    }
+}
@@ -4,10 +4,57 @@
 //! 
 //! The generalization that is used here only works for data which contains no pointers to other
 //! data. As such, the traits are only implemented for types which implement `Copy` and for slices
-//! whose contents implement `Copy`.
+//! whose contents implement `Copy`. Viewing a value as bytes additionally requires that it contains
+//! no padding, since padding bytes are uninitialized, so `AsBytes` is only implemented for types
+//! which implement `NoUninit`.
+//! 
+//! Strings are supported too: `str` is decoded after checking that it is valid UTF-8, `CStr` up to
+//! its nul terminator, and `NulPaddedStr<N>` stands in for `char name[N]` fields of C structs.
 //! 
 //! This crate makes no guarantees about portability across systems; it simply encodes the raw
-//! bytes of values.
+//! bytes of values. For data shared between systems, the types in `endian` store numbers in a
+//! fixed byte order.
+//! 
+//! Decoding a `Copy` type from bytes is unsafe, because not every bit pattern is a valid value of
+//! every type; a `bool` must be 0 or 1, for example. Types for which any bit pattern is valid
+//! implement `AnyBitPattern` and can be decoded without `unsafe` through `SafeWithBytes`. Types
+//! such as `bool`, `char` and `NonZeroU32` implement `CheckedBitPattern` instead, so that
+//! `SafeWithBytes` checks that the bytes make up a valid value before decoding them. Types which are
+//! valid when all of their bytes are zero implement `Zeroable`, which provides `zeroed` along with
+//! `zero_slice`, `with_zeroed_bytes` and, with the `alloc` feature enabled, `zeroed_box` and
+//! `zeroed_vec`.
+//! 
+//! With the `derive` feature enabled, `#[derive(AsBytes)]` implements `NoUninit` and
+//! `#[derive(WithBytes)]` implements `AnyBitPattern` for `#[repr(C)]` structs without padding.
+//! `#[derive(CheckedBitPattern)]` implements `CheckedBitPattern` for fieldless enums with an
+//! integer representation, rejecting bytes which do not match any discriminant.
+//! 
+//! To view one plain value as another of the same size, such as `&[u32; 4]` as `&[u8; 16]`, use
+//! `cast_ref` and `cast_mut`, which check the sizes and alignments when the crate is built, or
+//! `try_cast_ref`, which checks them at run time along with the validity of the result.
+//! `cast_slice` and `cast_slice_mut` do the same for slices, failing instead of dropping leftover
+//! bytes, and `split_cast_slice` returns any misaligned or leftover bytes alongside the result.
+//! 
+//! Each trait has a counterpart ending in `Mut` which works with mutable references instead, so
+//! that values inside a buffer can be modified in place.
+//! 
+//! To parse several values one after another, `try_split_prefix` and its relatives decode a value
+//! from one end of the bytes and return the rest of the bytes along with it. `ByteReader` keeps track
+//! of the position in the bytes for you, and `ByteWriter` does the same for writing values into a
+//! buffer. Both can store slices after a `LengthPrefix` holding the number of elements, so that
+//! several slices of different lengths can share one buffer. `StridedSlice` decodes values spaced
+//! further apart than their size, such as records interleaved with other data.
+//! 
+//! A reference to a value must be properly aligned for its type. `with_bytes` leaves this up to
+//! the caller, while `try_with_bytes` checks the alignment of the bytes and refuses to decode
+//! them if they are not aligned. Wrapping a type in `Unaligned` lowers its alignment to 1, so that
+//! it can be decoded from any offset. With the `alloc` feature enabled, `AlignedBuf` provides a
+//! growable buffer of bytes with a chosen alignment to decode values from. When avoiding a copy
+//! does not matter, `read_from` and `read_slice_into` copy values out of bytes of any alignment,
+//! and `IntoByteArray` and `FromByteArray` convert values to and from byte arrays whose length must
+//! match the size of the value, which is checked when the crate is built.
+//! With the `std` feature enabled, `ReadBytesExt` and `WriteBytesExt` read and write values through
+//! `std::io`.
 //! 
 //! #### It's all the same block of memory
 //! ```rust
@@ -21,13 +68,113 @@
 //! assert!(ptr::eq(unsafe { <[i32; 2]>::with_bytes(arr.as_bytes()) }, &arr));
 //! ```
 
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "derive")]
+extern crate as_with_bytes_derive;
+
+mod array;
+#[cfg(feature = "alloc")]
+mod buf;
+mod cast;
+mod copy;
+pub mod endian;
+mod error;
+#[cfg(feature = "std")]
+mod io;
+mod mutable;
+mod pod;
+mod prefix;
+mod reader;
+mod split;
+mod strided;
+mod string;
+mod unaligned;
+mod writer;
+mod zeroed;
+
+pub use array::{FromByteArray, IntoByteArray};
+#[cfg(feature = "alloc")]
+pub use buf::{Align16, Align2, Align32, Align4, Align64, Align8, AlignedBuf};
+pub use cast::{cast_mut, cast_ref, cast_slice, cast_slice_mut, split_cast_slice, try_cast_ref};
+pub use copy::{read_from, read_slice_into};
+pub use error::{CastError, DecodeError, WriteError};
+#[cfg(feature = "std")]
+pub use io::{ReadBytesExt, WriteBytesExt};
+pub use mutable::{
+    AsBytesMut, CheckedWithBytesMut, SafeWithBytesMut, TryWithBytesMut, WithBytesMut,
+};
+pub use pod::{AnyBitPattern, CheckedBitPattern, NoUninit, Zeroable};
+pub use prefix::{ByteOrder, LengthPrefix};
+pub use reader::ByteReader;
+pub use split::{
+    try_split_prefix, try_split_prefix_slice, try_split_suffix, try_split_suffix_slice, with_bytes_n,
+};
+pub use strided::{StridedIter, StridedSlice};
+pub use string::NulPaddedStr;
+pub use unaligned::Unaligned;
+pub use writer::ByteWriter;
+#[cfg(feature = "alloc")]
+pub use writer::AlignedBufWriter;
+pub use zeroed::{with_zeroed_bytes, zero_slice};
+#[cfg(feature = "alloc")]
+pub use zeroed::{zeroed_box, zeroed_vec};
+
+#[cfg(feature = "derive")]
+pub use as_with_bytes_derive::{AsBytes, CheckedBitPattern, WithBytes};
+
 use core::mem;
 use core::slice;
 
+use pod::BitsLayout;
+
 /// A trait used for converting into bytes.
+/// 
+/// This is implemented for `T` and `[T]` where `T: NoUninit`, as well as
+/// for `str` and `CStr`.
 pub trait AsBytes {
     /// Returns a byte slice representation of `self`.
     fn as_bytes(&self) -> &[u8];
+    
+    /// Copies the bytes of `self` into `dest`, which must have exactly the
+    /// same length. Returns the number of bytes written.
+    #[inline]
+    fn write_to(&self, dest: &mut [u8]) -> Result<usize, WriteError> {
+        let bytes = self.as_bytes();
+        if dest.len() > bytes.len() {
+            return Err(WriteError::TooLong { expected: bytes.len(), actual: dest.len() });
+        }
+        self.write_to_prefix(dest)
+    }
+    
+    /// Copies the bytes of `self` into the start of `dest`, leaving the rest
+    /// of `dest` alone. Returns the number of bytes written.
+    #[inline]
+    fn write_to_prefix(&self, dest: &mut [u8]) -> Result<usize, WriteError> {
+        let bytes = self.as_bytes();
+        match dest.get_mut(..bytes.len()) {
+            Some(prefix) => prefix.copy_from_slice(bytes),
+            None => return Err(WriteError::TooShort { expected: bytes.len(), actual: dest.len() }),
+        }
+        Ok(bytes.len())
+    }
+    
+    /// Copies the bytes of `self` into the end of `dest`, leaving the rest
+    /// of `dest` alone. Returns the number of bytes written.
+    #[inline]
+    fn write_to_suffix(&self, dest: &mut [u8]) -> Result<usize, WriteError> {
+        let bytes = self.as_bytes();
+        let start = match dest.len().checked_sub(bytes.len()) {
+            Some(start) => start,
+            None => return Err(WriteError::TooShort { expected: bytes.len(), actual: dest.len() }),
+        };
+        dest[start..].copy_from_slice(bytes);
+        Ok(bytes.len())
+    }
 }
 
 /// A trait used for converting from bytes.
@@ -36,45 +183,148 @@ pub trait WithBytes {
     /// 
     /// # Panics
     /// This function panics when a slice containing a zero-sized
-    /// type is requested.
+    /// type is requested. Use `with_bytes_n` to decode those.
     /// 
-    /// # Unsafe
-    /// This function is unsafe for two reasons: Firstly, If the
+    /// # Safety
+    /// This function is unsafe for three reasons: Firstly, If the
     /// length of `bytes` is shorter than `size_of::<Self>`,
-    /// arbitrary memory is read. Secondly, invalid values can
-    /// be returned, such as an instance of an empty enum.
-    /// This method will work fine as long as you are careful to
-    /// avoid both scenarios.
-    unsafe fn with_bytes<'a>(bytes: &'a [u8]) -> &'a Self;
+    /// arbitrary memory is read. Secondly, if `bytes` does not
+    /// start at an address which is a multiple of the alignment
+    /// of `Self`, a misaligned reference is created. Thirdly,
+    /// invalid values can be returned, such as an instance of an
+    /// empty enum. This method will work fine as long as you are
+    /// careful to avoid all three scenarios.
+    unsafe fn with_bytes(bytes: &[u8]) -> &Self;
 }
 
 
 /// A trait for converting from bytes while checking that the byte
-/// slice is long enough.
+/// slice is long enough and properly aligned.
 pub trait TryWithBytes {
-    /// Returns `Some(&Self)` if there are enough bytes to encode `Self`,
-    /// or `None` otherwise.
+    /// Returns `Some(&Self)` if there are enough bytes to encode `Self`
+    /// and they are aligned for `Self`, or `None` otherwise.
     /// 
-    /// # Unsafe
+    /// # Safety
     /// While this protects against reading from memory beyond the boundary
-    /// of the bytes, it can still produce invalid data for some types such
-    /// as enums. It will work as long as whatever you encode from a type,
-    /// you decode into that same type.
+    /// of the bytes and against misaligned references, it can still produce
+    /// invalid data for some types such as enums. It will work as long as
+    /// whatever you encode from a type, you decode into that same type.
+    /// For types implementing `CheckedBitPattern`, `SafeWithBytes` checks
+    /// the value as well and needs no `unsafe`.
     /// 
     /// #### Note
-    /// When used to decode dynamically sized slices, `Some` will almost always
-    /// be returned, since the slice will be empty if there is not enough data.
-    /// The only situation where `None` is ever returned is when you ask for a
-    /// slice containing a type with a size of zero, and who would want that?
-    unsafe fn try_with_bytes<'a>(bytes: &'a [u8]) -> Option<&'a Self>;
+    /// When used to decode dynamically sized slices, `Some` will be returned
+    /// for any aligned bytes, since the slice will be empty if there is not
+    /// enough data. `None` is returned when the bytes are misaligned or when
+    /// you ask for a slice containing a type with a size of zero, since the
+    /// number of elements cannot be worked out from the length of the bytes.
+    /// `with_bytes_n` takes the number of elements explicitly and works for
+    /// those types as well.
+    unsafe fn try_with_bytes(bytes: &[u8]) -> Option<&Self>;
 }
 
-impl <T: Copy> AsBytes for T {
+/// A trait for converting from bytes which reports why the conversion
+/// failed.
+pub trait CheckedWithBytes {
+    /// Returns `Ok(&Self)` under the same conditions as `try_with_bytes`
+    /// returns `Some`, or a `DecodeError` describing the problem otherwise.
+    /// 
+    /// # Safety
+    /// Like `try_with_bytes`, this can still produce invalid data for some
+    /// types such as enums.
+    unsafe fn checked_with_bytes(bytes: &[u8]) -> Result<&Self, DecodeError>;
+    
+    /// Works like `checked_with_bytes`, but also fails with
+    /// `DecodeError::TrailingBytes` if any bytes would be left over, meaning
+    /// that `bytes.len()` is not exactly `size_of::<Self>()`, or for slices,
+    /// not an exact multiple of the element size.
+    /// 
+    /// # Safety
+    /// The same requirements as for `checked_with_bytes` apply.
+    unsafe fn checked_with_bytes_exact(bytes: &[u8]) -> Result<&Self, DecodeError>;
+}
+
+/// A trait for converting from bytes which is safe because the bytes are
+/// checked to make up a valid value of the decoded type.
+/// 
+/// This is implemented for `T` and `[T]` where `T: CheckedBitPattern`,
+/// which includes every `AnyBitPattern` type. Bytes which are not a valid
+/// value are rejected with `DecodeError::InvalidBitPattern`.
+pub trait SafeWithBytes {
+    /// Returns `Ok(&Self)` under the same conditions as `checked_with_bytes`,
+    /// or a `DecodeError` describing the problem otherwise.
+    fn safe_with_bytes(bytes: &[u8]) -> Result<&Self, DecodeError>;
+    
+    /// Returns `Ok(&Self)` under the same conditions as
+    /// `checked_with_bytes_exact`, or a `DecodeError` describing the problem
+    /// otherwise.
+    fn safe_with_bytes_exact(bytes: &[u8]) -> Result<&Self, DecodeError>;
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use core::mem::{align_of, size_of};
+    
+    use {AnyBitPattern, NoUninit};
+    
+    #[inline]
+    pub fn assert_no_uninit<T: NoUninit>() {}
+    
+    #[inline]
+    pub fn assert_any_bit_pattern<T: AnyBitPattern>() {}
+}
+
+/// Checks that `bits` make up a valid `T`.
+#[inline]
+fn check_bit_pattern<T: CheckedBitPattern>(bits: &T::Bits) -> Result<(), DecodeError> {
+    if T::is_valid_bit_pattern(bits) {
+        Ok(())
+    } else {
+        Err(DecodeError::InvalidBitPattern)
+    }
+}
+
+/// Checks that `bytes` starts at an address suitable for a `T`.
+#[inline]
+fn check_alignment<T>(bytes: &[u8]) -> Result<(), DecodeError> {
+    let align = mem::align_of::<T>();
+    let offset = bytes.as_ptr() as usize % align;
+    if offset == 0 {
+        Ok(())
+    } else {
+        Err(DecodeError::Misaligned { align, offset })
+    }
+}
+
+/// Checks that `len` bytes do not go past the end of a `T`.
+#[inline]
+fn check_no_trailing<T>(len: usize) -> Result<(), DecodeError> {
+    let size = mem::size_of::<T>();
+    if len > size {
+        Err(DecodeError::TrailingBytes { expected: size, actual: len })
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that `len` bytes do not go past the end of the last `T` which
+/// fits in them.
+#[inline]
+fn check_no_trailing_elements<T>(len: usize) -> Result<(), DecodeError> {
+    let remainder = len.checked_rem(mem::size_of::<T>()).unwrap_or(0);
+    if remainder != 0 {
+        Err(DecodeError::TrailingBytes { expected: len - remainder, actual: len })
+    } else {
+        Ok(())
+    }
+}
+
+impl <T: NoUninit> AsBytes for T {
     #[inline]
-    fn as_bytes<'a>(&self) -> &[u8] {
+    fn as_bytes(&self) -> &[u8] {
         unsafe {
             slice::from_raw_parts(
-                mem::transmute::<_, *const u8>(self),
+                self as *const T as *const u8,
                 mem::size_of::<T>(),
             )
         }
@@ -83,29 +333,59 @@ impl <T: Copy> AsBytes for T {
 
 impl <T: Copy> WithBytes for T {
     #[inline]
-    unsafe fn with_bytes<'a>(bytes: &'a [u8]) -> &'a T {
-        mem::transmute::<_, &'a T>(bytes.as_ptr())
+    unsafe fn with_bytes(bytes: &[u8]) -> &T {
+        &*(bytes.as_ptr() as *const T)
     }
 }
 
 impl <T: Copy> TryWithBytes for T {
     #[inline]
-    unsafe fn try_with_bytes<'a>(bytes: &'a [u8]) -> Option<&'a T> {
-        if bytes.len() < mem::size_of::<T>() {
-            None
-        } else {
-            Some(T::with_bytes(bytes))
+    unsafe fn try_with_bytes(bytes: &[u8]) -> Option<&T> {
+        T::checked_with_bytes(bytes).ok()
+    }
+}
+
+impl <T: Copy> CheckedWithBytes for T {
+    #[inline]
+    unsafe fn checked_with_bytes(bytes: &[u8]) -> Result<&T, DecodeError> {
+        let size = mem::size_of::<T>();
+        if bytes.len() < size {
+            return Err(DecodeError::TooShort { expected: size, actual: bytes.len() });
         }
+        check_alignment::<T>(bytes)?;
+        Ok(T::with_bytes(bytes))
+    }
+    
+    #[inline]
+    unsafe fn checked_with_bytes_exact(bytes: &[u8]) -> Result<&T, DecodeError> {
+        check_no_trailing::<T>(bytes.len())?;
+        T::checked_with_bytes(bytes)
     }
 }
 
-impl <T: Copy> AsBytes for [T] {
+impl <T: CheckedBitPattern> SafeWithBytes for T {
+    #[inline]
+    fn safe_with_bytes(bytes: &[u8]) -> Result<&T, DecodeError> {
+        let () = BitsLayout::<T>::OK;
+        let bits = unsafe { T::Bits::checked_with_bytes(bytes)? };
+        check_bit_pattern::<T>(bits)?;
+        Ok(unsafe { &*(bits as *const T::Bits as *const T) })
+    }
+    
     #[inline]
-    fn as_bytes<'a>(&self) -> &[u8] {
+    fn safe_with_bytes_exact(bytes: &[u8]) -> Result<&T, DecodeError> {
+        check_no_trailing::<T>(bytes.len())?;
+        T::safe_with_bytes(bytes)
+    }
+}
+
+impl <T: NoUninit> AsBytes for [T] {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
         unsafe {
             slice::from_raw_parts(
-                mem::transmute::<_, *const u8>(self.as_ptr()),
-                self.len() * mem::size_of::<T>(),
+                self.as_ptr() as *const u8,
+                mem::size_of_val(self),
             )
         }
     }
@@ -113,22 +393,52 @@ impl <T: Copy> AsBytes for [T] {
 
 impl <T: Copy> WithBytes for [T] {    
     #[inline]
-    unsafe fn with_bytes<'a>(bytes: &'a [u8]) -> &'a [T] {
-        slice::from_raw_parts(
-            mem::transmute::<_, *const T>(bytes.as_ptr()),
-            bytes.len() / mem::size_of::<T>(),
-        )
+    unsafe fn with_bytes(bytes: &[u8]) -> &[T] {
+        let size = mem::size_of::<T>();
+        assert!(size != 0, "cannot decode a slice of a zero-sized type; use `with_bytes_n`");
+        slice::from_raw_parts(bytes.as_ptr() as *const T, bytes.len() / size)
     }
 }
 
 impl <T: Copy> TryWithBytes for [T] {
     #[inline]
-    unsafe fn try_with_bytes<'a>(bytes: &'a [u8]) -> Option<&'a [T]> {
-        if mem::size_of::<T>() > 0 {
-            Some(<[T]>::with_bytes(bytes))
-        } else {
-            None
+    unsafe fn try_with_bytes(bytes: &[u8]) -> Option<&[T]> {
+        <[T]>::checked_with_bytes(bytes).ok()
+    }
+}
+
+impl <T: Copy> CheckedWithBytes for [T] {
+    #[inline]
+    unsafe fn checked_with_bytes(bytes: &[u8]) -> Result<&[T], DecodeError> {
+        if mem::size_of::<T>() == 0 {
+            return Err(DecodeError::ZeroSized);
         }
+        check_alignment::<T>(bytes)?;
+        Ok(<[T]>::with_bytes(bytes))
+    }
+    
+    #[inline]
+    unsafe fn checked_with_bytes_exact(bytes: &[u8]) -> Result<&[T], DecodeError> {
+        check_no_trailing_elements::<T>(bytes.len())?;
+        <[T]>::checked_with_bytes(bytes)
+    }
+}
+
+impl <T: CheckedBitPattern> SafeWithBytes for [T] {
+    #[inline]
+    fn safe_with_bytes(bytes: &[u8]) -> Result<&[T], DecodeError> {
+        let () = BitsLayout::<T>::OK;
+        let bits = unsafe { <[T::Bits]>::checked_with_bytes(bytes)? };
+        for bits in bits {
+            check_bit_pattern::<T>(bits)?;
+        }
+        Ok(unsafe { slice::from_raw_parts(bits.as_ptr() as *const T, bits.len()) })
+    }
+    
+    #[inline]
+    fn safe_with_bytes_exact(bytes: &[u8]) -> Result<&[T], DecodeError> {
+        check_no_trailing_elements::<T>(bytes.len())?;
+        <[T]>::safe_with_bytes(bytes)
     }
 }
 
@@ -151,15 +461,138 @@ mod tests {
     
     #[test]
     fn try_with_bytes_works() {
-        assert_eq!(unsafe { u64::try_with_bytes(&[0; 8]) }, Some(&0));
-        assert_eq!(unsafe { u64::try_with_bytes(&[0; 7]) }, None);
+        let n: u64 = 0;
+        
+        assert_eq!(unsafe { u64::try_with_bytes(n.as_bytes()) }, Some(&0));
+        assert_eq!(unsafe { u64::try_with_bytes(&n.as_bytes()[..7]) }, None);
+    }
+    
+    #[test]
+    fn try_with_bytes_checks_alignment() {
+        let arr = [0u64; 2];
+        let bytes = arr.as_bytes();
+        
+        assert_eq!(unsafe { u64::try_with_bytes(&bytes[8..]) }, Some(&0));
+        assert_eq!(unsafe { u64::try_with_bytes(&bytes[1..]) }, None);
+        assert_eq!(unsafe { <[u32]>::try_with_bytes(&bytes[4..]) }, Some(&[0; 3][..]));
+        assert_eq!(unsafe { <[u32]>::try_with_bytes(&bytes[2..]) }, None);
+    }
+    
+    #[test]
+    fn checked_with_bytes_reports_errors() {
+        let arr = [0u32; 2];
+        let bytes = arr.as_bytes();
+        
+        assert_eq!(unsafe { u32::checked_with_bytes(&bytes[4..]) }, Ok(&0));
+        assert_eq!(
+            unsafe { u64::checked_with_bytes(&bytes[4..]) },
+            Err(DecodeError::TooShort { expected: 8, actual: 4 }),
+        );
+        assert_eq!(
+            unsafe { <[u32]>::checked_with_bytes(&bytes[1..]) },
+            Err(DecodeError::Misaligned { align: 4, offset: 1 }),
+        );
+        assert_eq!(unsafe { <[()]>::checked_with_bytes(bytes) }, Err(DecodeError::ZeroSized));
+    }
+    
+    #[test]
+    fn safe_with_bytes_works() {
+        let arr = [1u16, 2, 3, 4];
+        let bytes = arr.as_bytes();
+        
+        assert_eq!(<[(u16, u16); 2]>::safe_with_bytes(bytes), Ok(&[(1, 2), (3, 4)]));
+        assert_eq!(<[(u16, u16)]>::safe_with_bytes(&bytes[..6]), Ok(&[(1, 2)][..]));
+        assert_eq!(
+            f64::safe_with_bytes(&bytes[..4]),
+            Err(DecodeError::TooShort { expected: 8, actual: 4 }),
+        );
+    }
+    
+    #[test]
+    fn checked_bit_patterns_work() {
+        use core::num::NonZeroU32;
+        
+        assert_eq!(<[bool]>::safe_with_bytes(&[0, 1]), Ok(&[false, true][..]));
+        assert_eq!(bool::safe_with_bytes(&[2]), Err(DecodeError::InvalidBitPattern));
+        assert_eq!(<[bool]>::safe_with_bytes(&[1, 2]), Err(DecodeError::InvalidBitPattern));
+        assert_eq!(char::safe_with_bytes('é'.as_bytes()), Ok(&'é'));
+        assert_eq!(
+            char::safe_with_bytes(0xd800u32.as_bytes()),
+            Err(DecodeError::InvalidBitPattern),
+        );
+        assert_eq!(
+            NonZeroU32::safe_with_bytes(0u32.as_bytes()),
+            Err(DecodeError::InvalidBitPattern),
+        );
+        assert_eq!(<Option<NonZeroU32>>::safe_with_bytes(0u32.as_bytes()), Ok(&None));
+        assert_eq!(<Option<NonZeroU32>>::safe_with_bytes(5u32.as_bytes()), Ok(&NonZeroU32::new(5)));
+    }
+    
+    #[test]
+    fn no_uninit_structs_work() {
+        no_uninit! {
+            #[derive(Clone, Copy)]
+            #[repr(C)]
+            struct Pair {
+                a: u16,
+                b: [u8; 2],
+            }
+        }
+        
+        no_uninit! {
+            #[derive(Clone, Copy)]
+            struct Wrapper(u32);
+        }
+        
+        let pair = Pair { a: 0, b: [1, 2] };
+        let wrapper = Wrapper(0);
+        
+        assert_eq!(&pair.as_bytes()[2..], &[1, 2]);
+        assert_eq!(wrapper.as_bytes(), wrapper.0.as_bytes());
+    }
+    
+    #[test]
+    fn exact_decoding_rejects_trailing_bytes() {
+        let arr = [0u64; 2];
+        let bytes = arr.as_bytes();
+        
+        assert_eq!(u64::safe_with_bytes_exact(&bytes[..8]), Ok(&0));
+        assert_eq!(
+            u64::safe_with_bytes_exact(&bytes[..9]),
+            Err(DecodeError::TrailingBytes { expected: 8, actual: 9 }),
+        );
+        assert_eq!(<[u16]>::safe_with_bytes_exact(&bytes[..6]), Ok(&[0; 3][..]));
+        assert_eq!(
+            <[u16]>::safe_with_bytes_exact(&bytes[..7]),
+            Err(DecodeError::TrailingBytes { expected: 6, actual: 7 }),
+        );
+        assert_eq!(unsafe { <[()]>::checked_with_bytes_exact(bytes) }, Err(DecodeError::ZeroSized));
+    }
+    
+    #[test]
+    fn write_to_works() {
+        let mut frame = [0u8; 6];
+        
+        assert_eq!(0x0101u16.write_to_prefix(&mut frame), Ok(2));
+        assert_eq!([2u8, 3].write_to_suffix(&mut frame), Ok(2));
+        assert_eq!(frame, [1, 1, 0, 0, 2, 3]);
+        assert_eq!(7u32.write_to(&mut frame[1..5]), Ok(4));
+        assert_eq!(&frame[1..5], 7u32.as_bytes());
+        assert_eq!(
+            7u32.write_to(&mut frame),
+            Err(WriteError::TooLong { expected: 4, actual: 6 }),
+        );
+        assert_eq!(
+            [0u16; 4][..].write_to_suffix(&mut frame),
+            Err(WriteError::TooShort { expected: 8, actual: 6 }),
+        );
     }
     
     #[test]
     fn unsized_slices_work() {
-        let arr = [0u8; 7];
+        let arr = [0u16; 4];
         
-        assert_eq!(unsafe { <[u16]>::with_bytes(&arr) }, &[0; 3]);
+        assert_eq!(unsafe { <[u16]>::with_bytes(&arr.as_bytes()[..7]) }, &[0; 3]);
     }
     
     #[test]
@@ -0,0 +1,234 @@
+use core::mem;
+use core::slice;
+
+use {check_alignment, check_no_trailing, check_no_trailing_elements};
+use {AnyBitPattern, DecodeError, NoUninit};
+
+/// A trait used for converting into mutable bytes.
+///
+/// This is implemented for `T` and `[T]` where `T: NoUninit + AnyBitPattern`,
+/// since the bytes must be initialized to be read and any bytes written must
+/// leave behind a valid value.
+pub trait AsBytesMut {
+    /// Returns a mutable byte slice representation of `self`.
+    fn as_bytes_mut(&mut self) -> &mut [u8];
+}
+
+/// A trait used for converting from mutable bytes.
+pub trait WithBytesMut {
+    /// Returns a mutable `Self` representation of the given slice of bytes.
+    ///
+    /// # Panics
+    /// This function panics when a slice containing a zero-sized
+    /// type is requested.
+    ///
+    /// # Safety
+    /// The same requirements as for `with_bytes` apply. In addition, any
+    /// value written through the returned reference must leave the bytes
+    /// initialized, so `Self` should not contain padding.
+    unsafe fn with_bytes_mut(bytes: &mut [u8]) -> &mut Self;
+}
+
+/// A trait for converting from mutable bytes while checking that the byte
+/// slice is long enough and properly aligned.
+pub trait TryWithBytesMut {
+    /// Returns `Some(&mut Self)` under the same conditions as
+    /// `try_with_bytes` returns `Some`, or `None` otherwise.
+    ///
+    /// # Safety
+    /// The same requirements as for `try_with_bytes` apply. In addition, any
+    /// value written through the returned reference must leave the bytes
+    /// initialized.
+    unsafe fn try_with_bytes_mut(bytes: &mut [u8]) -> Option<&mut Self>;
+}
+
+/// A trait for converting from mutable bytes which reports why the
+/// conversion failed.
+pub trait CheckedWithBytesMut {
+    /// Returns `Ok(&mut Self)` under the same conditions as
+    /// `checked_with_bytes` returns `Ok`, or a `DecodeError` describing the
+    /// problem otherwise.
+    ///
+    /// # Safety
+    /// The same requirements as for `try_with_bytes_mut` apply.
+    unsafe fn checked_with_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, DecodeError>;
+
+    /// Works like `checked_with_bytes_mut`, but also fails if any bytes would
+    /// be left over, like `checked_with_bytes_exact`.
+    ///
+    /// # Safety
+    /// The same requirements as for `try_with_bytes_mut` apply.
+    unsafe fn checked_with_bytes_mut_exact(bytes: &mut [u8]) -> Result<&mut Self, DecodeError>;
+}
+
+/// A trait for converting from mutable bytes which is safe because every
+/// bit pattern of the decoded type is valid and the type has no padding.
+///
+/// This is implemented for `T` and `[T]` where `T: NoUninit + AnyBitPattern`.
+pub trait SafeWithBytesMut {
+    /// Returns `Ok(&mut Self)` under the same conditions as
+    /// `checked_with_bytes_mut`, or a `DecodeError` describing the problem
+    /// otherwise.
+    fn safe_with_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, DecodeError>;
+
+    /// Returns `Ok(&mut Self)` under the same conditions as
+    /// `checked_with_bytes_mut_exact`, or a `DecodeError` describing the
+    /// problem otherwise.
+    fn safe_with_bytes_mut_exact(bytes: &mut [u8]) -> Result<&mut Self, DecodeError>;
+}
+
+impl <T: NoUninit + AnyBitPattern> AsBytesMut for T {
+    #[inline]
+    fn as_bytes_mut(&mut self) -> &mut [u8] {
+        unsafe {
+            slice::from_raw_parts_mut(
+                self as *mut T as *mut u8,
+                mem::size_of::<T>(),
+            )
+        }
+    }
+}
+
+impl <T: Copy> WithBytesMut for T {
+    #[inline]
+    unsafe fn with_bytes_mut(bytes: &mut [u8]) -> &mut T {
+        &mut *(bytes.as_mut_ptr() as *mut T)
+    }
+}
+
+impl <T: Copy> TryWithBytesMut for T {
+    #[inline]
+    unsafe fn try_with_bytes_mut(bytes: &mut [u8]) -> Option<&mut T> {
+        T::checked_with_bytes_mut(bytes).ok()
+    }
+}
+
+impl <T: Copy> CheckedWithBytesMut for T {
+    #[inline]
+    unsafe fn checked_with_bytes_mut(bytes: &mut [u8]) -> Result<&mut T, DecodeError> {
+        let size = mem::size_of::<T>();
+        if bytes.len() < size {
+            return Err(DecodeError::TooShort { expected: size, actual: bytes.len() });
+        }
+        check_alignment::<T>(bytes)?;
+        Ok(T::with_bytes_mut(bytes))
+    }
+
+    #[inline]
+    unsafe fn checked_with_bytes_mut_exact(bytes: &mut [u8]) -> Result<&mut T, DecodeError> {
+        check_no_trailing::<T>(bytes.len())?;
+        T::checked_with_bytes_mut(bytes)
+    }
+}
+
+impl <T: NoUninit + AnyBitPattern> SafeWithBytesMut for T {
+    #[inline]
+    fn safe_with_bytes_mut(bytes: &mut [u8]) -> Result<&mut T, DecodeError> {
+        unsafe { T::checked_with_bytes_mut(bytes) }
+    }
+
+    #[inline]
+    fn safe_with_bytes_mut_exact(bytes: &mut [u8]) -> Result<&mut T, DecodeError> {
+        unsafe { T::checked_with_bytes_mut_exact(bytes) }
+    }
+}
+
+impl <T: NoUninit + AnyBitPattern> AsBytesMut for [T] {
+    #[inline]
+    fn as_bytes_mut(&mut self) -> &mut [u8] {
+        unsafe {
+            slice::from_raw_parts_mut(
+                self.as_mut_ptr() as *mut u8,
+                mem::size_of_val(self),
+            )
+        }
+    }
+}
+
+impl <T: Copy> WithBytesMut for [T] {
+    #[inline]
+    unsafe fn with_bytes_mut(bytes: &mut [u8]) -> &mut [T] {
+        let size = mem::size_of::<T>();
+        assert!(size != 0, "cannot decode a slice of a zero-sized type; use `with_bytes_n`");
+        slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, bytes.len() / size)
+    }
+}
+
+impl <T: Copy> TryWithBytesMut for [T] {
+    #[inline]
+    unsafe fn try_with_bytes_mut(bytes: &mut [u8]) -> Option<&mut [T]> {
+        <[T]>::checked_with_bytes_mut(bytes).ok()
+    }
+}
+
+impl <T: Copy> CheckedWithBytesMut for [T] {
+    #[inline]
+    unsafe fn checked_with_bytes_mut(bytes: &mut [u8]) -> Result<&mut [T], DecodeError> {
+        if mem::size_of::<T>() == 0 {
+            return Err(DecodeError::ZeroSized);
+        }
+        check_alignment::<T>(bytes)?;
+        Ok(<[T]>::with_bytes_mut(bytes))
+    }
+
+    #[inline]
+    unsafe fn checked_with_bytes_mut_exact(bytes: &mut [u8]) -> Result<&mut [T], DecodeError> {
+        check_no_trailing_elements::<T>(bytes.len())?;
+        <[T]>::checked_with_bytes_mut(bytes)
+    }
+}
+
+impl <T: NoUninit + AnyBitPattern> SafeWithBytesMut for [T] {
+    #[inline]
+    fn safe_with_bytes_mut(bytes: &mut [u8]) -> Result<&mut [T], DecodeError> {
+        unsafe { <[T]>::checked_with_bytes_mut(bytes) }
+    }
+
+    #[inline]
+    fn safe_with_bytes_mut_exact(bytes: &mut [u8]) -> Result<&mut [T], DecodeError> {
+        unsafe { <[T]>::checked_with_bytes_mut_exact(bytes) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AsBytes;
+
+    #[test]
+    fn patch_in_place() {
+        let mut arr = [0u32; 2];
+
+        *u32::safe_with_bytes_mut(&mut arr.as_bytes_mut()[4..]).unwrap() = 7;
+        assert_eq!(arr, [0, 7]);
+    }
+
+    #[test]
+    fn as_bytes_mut_same_as_as_bytes() {
+        let mut arr = [1u16, 2, 3];
+        let expected = [1u16, 2, 3];
+
+        assert_eq!(arr.as_bytes_mut(), expected.as_bytes());
+        assert_eq!(<[u16]>::safe_with_bytes_mut(arr.as_bytes_mut()), Ok(&mut [1, 2, 3][..]));
+    }
+
+    #[test]
+    fn checked_with_bytes_mut_reports_errors() {
+        let mut arr = [0u32; 2];
+        let bytes = arr.as_bytes_mut();
+
+        assert_eq!(unsafe { u32::try_with_bytes_mut(&mut bytes[2..]) }, None);
+        assert_eq!(
+            unsafe { u64::checked_with_bytes_mut(&mut bytes[4..]) },
+            Err(DecodeError::TooShort { expected: 8, actual: 4 }),
+        );
+        assert_eq!(
+            unsafe { <[u32]>::checked_with_bytes_mut(&mut bytes[1..]) },
+            Err(DecodeError::Misaligned { align: 4, offset: 1 }),
+        );
+        assert_eq!(
+            <[u32]>::safe_with_bytes_mut_exact(&mut bytes[..6]),
+            Err(DecodeError::TrailingBytes { expected: 4, actual: 6 }),
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Marker traits describing which bytes make up a valid value of a type.
+
+use core::marker::PhantomData;
+use core::mem;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
+    NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+/// A marker trait for types which are valid for any bit pattern.
+///
+/// Types implementing this trait can be decoded from arbitrary bytes
+/// without producing invalid values, which is what makes `SafeWithBytes`
+/// safe to call.
+///
+/// # Safety
+/// Every possible sequence of `size_of::<Self>()` initialized bytes must be
+/// a valid value of `Self`. This rules out types such as `bool`, `char`,
+/// enums and references.
+pub unsafe trait AnyBitPattern: Copy + 'static {}
+
+macro_rules! impl_any_bit_pattern {
+    ($($ty:ty),*) => {
+        $(unsafe impl AnyBitPattern for $ty {})*
+    };
+}
+
+impl_any_bit_pattern!(
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64,
+    ()
+);
+
+unsafe impl <T: AnyBitPattern, const N: usize> AnyBitPattern for [T; N] {}
+
+macro_rules! impl_any_bit_pattern_tuples {
+    ($(($($name:ident),+))*) => {
+        $(unsafe impl <$($name: AnyBitPattern),+> AnyBitPattern for ($($name,)+) {})*
+    };
+}
+
+impl_any_bit_pattern_tuples!(
+    (A)
+    (A, B)
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+    (A, B, C, D, E, F, G)
+    (A, B, C, D, E, F, G, H)
+    (A, B, C, D, E, F, G, H, I)
+    (A, B, C, D, E, F, G, H, I, J)
+    (A, B, C, D, E, F, G, H, I, J, K)
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+);
+
+/// A marker trait for types which contain no uninitialized bytes.
+///
+/// Types implementing this trait can be viewed as bytes with `AsBytes`.
+/// Structs can implement it through the `no_uninit!` macro, which checks
+/// at compile time that they contain no padding.
+///
+/// # Safety
+/// Every byte of every value of `Self` must be initialized, so `Self`
+/// must not contain padding bytes or `MaybeUninit`s, and must not be a
+/// union.
+pub unsafe trait NoUninit: Copy + 'static {}
+
+macro_rules! impl_no_uninit {
+    ($($ty:ty),*) => {
+        $(unsafe impl NoUninit for $ty {})*
+    };
+}
+
+impl_no_uninit!(
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64,
+    bool, char,
+    ()
+);
+
+unsafe impl <T: NoUninit, const N: usize> NoUninit for [T; N] {}
+
+/// A trait for types which are valid for some bit patterns but not others.
+///
+/// Decoding such a type through `SafeWithBytes` first decodes the bytes as
+/// `Bits`, a type of the same layout for which any bit pattern is valid,
+/// and then asks `is_valid_bit_pattern` whether they make a valid `Self`.
+/// Every `AnyBitPattern` type implements this trait with `Bits = Self`.
+///
+/// With the `derive` feature enabled, `#[derive(CheckedBitPattern)]`
+/// implements it for fieldless enums with an integer representation such
+/// as `#[repr(u8)]`.
+///
+/// # Safety
+/// `Bits` must have the same size and alignment as `Self`, and any value of
+/// `Bits` for which `is_valid_bit_pattern` returns `true` must have the same
+/// bytes as a valid value of `Self`.
+pub unsafe trait CheckedBitPattern: Copy + 'static {
+    /// A type with the same layout as `Self` which is valid for any bit
+    /// pattern.
+    type Bits: AnyBitPattern;
+
+    /// Returns whether `bits` is a valid value of `Self`.
+    fn is_valid_bit_pattern(bits: &Self::Bits) -> bool;
+}
+
+/// Compile-time check that `T::Bits` has the same layout as `T`, so that an
+/// incorrect implementation of `CheckedBitPattern` fails to compile instead
+/// of reading out of bounds.
+pub(crate) struct BitsLayout<T>(PhantomData<T>);
+
+impl <T: CheckedBitPattern> BitsLayout<T> {
+    pub(crate) const OK: () = assert!(
+        mem::size_of::<T>() == mem::size_of::<T::Bits>()
+            && mem::align_of::<T>() == mem::align_of::<T::Bits>(),
+        "`CheckedBitPattern::Bits` must have the same size and alignment as `Self`",
+    );
+}
+
+unsafe impl <T: AnyBitPattern> CheckedBitPattern for T {
+    type Bits = T;
+
+    #[inline]
+    fn is_valid_bit_pattern(_bits: &T) -> bool {
+        true
+    }
+}
+
+unsafe impl CheckedBitPattern for bool {
+    type Bits = u8;
+
+    #[inline]
+    fn is_valid_bit_pattern(bits: &u8) -> bool {
+        *bits <= 1
+    }
+}
+
+unsafe impl CheckedBitPattern for char {
+    type Bits = u32;
+
+    #[inline]
+    fn is_valid_bit_pattern(bits: &u32) -> bool {
+        char::from_u32(*bits).is_some()
+    }
+}
+
+/// A trait for types for which a value with every byte set to zero is
+/// valid.
+///
+/// Every `AnyBitPattern` type implements this trait. So do `bool`, `char`
+/// and raw pointers, which are not valid for every bit pattern but are for
+/// all zeroes, giving `false`, `'\0'` and null respectively. Other types,
+/// such as structs containing those, can implement it by hand.
+///
+/// # Safety
+/// A value of `Self` whose bytes are all zero must be valid.
+pub unsafe trait Zeroable: Sized {
+    /// Returns a value with every byte set to zero.
+    #[inline]
+    fn zeroed() -> Self {
+        unsafe { mem::zeroed() }
+    }
+}
+
+unsafe impl <T: AnyBitPattern> Zeroable for T {}
+
+unsafe impl Zeroable for bool {}
+
+unsafe impl Zeroable for char {}
+
+unsafe impl <T> Zeroable for *const T {}
+
+unsafe impl <T> Zeroable for *mut T {}
+
+macro_rules! impl_non_zero {
+    ($($non_zero:ident($ty:ty)),*) => {
+        $(
+            unsafe impl CheckedBitPattern for $non_zero {
+                type Bits = $ty;
+
+                #[inline]
+                fn is_valid_bit_pattern(bits: &$ty) -> bool {
+                    *bits != 0
+                }
+            }
+
+            unsafe impl NoUninit for $non_zero {}
+
+            // `None` is guaranteed to be represented by zero.
+            unsafe impl AnyBitPattern for Option<$non_zero> {}
+
+            unsafe impl NoUninit for Option<$non_zero> {}
+        )*
+    };
+}
+
+impl_non_zero!(
+    NonZeroU8(u8), NonZeroU16(u16), NonZeroU32(u32),
+    NonZeroU64(u64), NonZeroU128(u128), NonZeroUsize(usize),
+    NonZeroI8(i8), NonZeroI16(i16), NonZeroI32(i32),
+    NonZeroI64(i64), NonZeroI128(i128), NonZeroIsize(isize)
+);
+
+/// Defines a struct and implements `NoUninit` for it.
+///
+/// Compilation fails if any field does not implement `NoUninit` or if the
+/// fields do not add up to the size of the struct, which would mean that
+/// the struct contains padding. Both structs with named fields and tuple
+/// structs are accepted, but not generic structs. The struct must also
+/// derive `Clone` and `Copy`.
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate as_with_bytes;
+///
+/// use as_with_bytes::AsBytes;
+///
+/// no_uninit! {
+///     #[derive(Clone, Copy)]
+///     #[repr(C)]
+///     pub struct Header {
+///         pub kind: u16,
+///         pub flags: u16,
+///         pub len: u32,
+///     }
+/// }
+///
+/// # fn main() {
+/// let header = Header { kind: 1, flags: 0, len: 12 };
+/// assert_eq!(header.as_bytes().len(), 8);
+/// # }
+/// ```
+///
+/// A struct with padding is rejected:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate as_with_bytes;
+///
+/// no_uninit! {
+///     #[derive(Clone, Copy)]
+///     #[repr(C)]
+///     struct Padded {
+///         small: u8,
+///         big: u32,
+///     }
+/// }
+///
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! no_uninit {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident : $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $($(#[$field_attr])* $field_vis $field: $ty),*
+        }
+
+        $crate::no_uninit!(@impl $name, $($ty),*);
+    };
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident (
+            $($(#[$field_attr:meta])* $field_vis:vis $ty:ty),* $(,)?
+        );
+    ) => {
+        $(#[$attr])*
+        $vis struct $name (
+            $($(#[$field_attr])* $field_vis $ty),*
+        );
+
+        $crate::no_uninit!(@impl $name, $($ty),*);
+    };
+    (@impl $name:ident, $($ty:ty),*) => {
+        const _: () = {
+            #[allow(dead_code)]
+            fn assert_fields_no_uninit() {
+                $($crate::__private::assert_no_uninit::<$ty>();)*
+            }
+
+            assert!(
+                $crate::__private::size_of::<$name>() == 0 $(+ $crate::__private::size_of::<$ty>())*,
+                concat!("`", stringify!($name), "` contains padding"),
+            );
+        };
+
+        unsafe impl $crate::NoUninit for $name {}
+    };
+}
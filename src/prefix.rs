@@ -0,0 +1,177 @@
+use core::convert::TryFrom;
+
+use {DecodeError, WriteError};
+
+/// The order of the bytes of a number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
+/// How the number of elements of a slice is stored in front of it.
+///
+/// This is used by `ByteReader::read_prefixed_slice` and the
+/// `push_prefixed_slice` methods of the writers, so that several slices of
+/// different lengths can follow each other in one buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LengthPrefix {
+    /// A single byte.
+    U8,
+    /// Two bytes in the given order.
+    U16(ByteOrder),
+    /// Four bytes in the given order.
+    U32(ByteOrder),
+    /// An unsigned LEB128 number, which takes up one byte for every seven
+    /// bits of the length.
+    Varint,
+}
+
+/// The most bytes a length prefix can take up.
+const MAX_PREFIX_LEN: usize = 10;
+
+impl LengthPrefix {
+    /// Returns the largest length which can be stored in this prefix.
+    pub fn max_len(self) -> usize {
+        match self {
+            LengthPrefix::U8 => u8::MAX as usize,
+            LengthPrefix::U16(_) => u16::MAX as usize,
+            LengthPrefix::U32(_) => usize::try_from(u32::MAX).unwrap_or(usize::MAX),
+            LengthPrefix::Varint => usize::MAX,
+        }
+    }
+
+    /// Encodes `len`, returning a buffer holding the encoded bytes at its
+    /// start and the number of bytes used.
+    pub(crate) fn encode(self, len: usize) -> Result<([u8; MAX_PREFIX_LEN], usize), WriteError> {
+        let max = self.max_len();
+        if len > max {
+            return Err(WriteError::LengthOverflow { len, max });
+        }
+        let mut buf = [0; MAX_PREFIX_LEN];
+        let used = match self {
+            LengthPrefix::U8 => {
+                buf[0] = len as u8;
+                1
+            }
+            LengthPrefix::U16(order) => {
+                buf[..2].copy_from_slice(&match order {
+                    ByteOrder::Little => (len as u16).to_le_bytes(),
+                    ByteOrder::Big => (len as u16).to_be_bytes(),
+                });
+                2
+            }
+            LengthPrefix::U32(order) => {
+                buf[..4].copy_from_slice(&match order {
+                    ByteOrder::Little => (len as u32).to_le_bytes(),
+                    ByteOrder::Big => (len as u32).to_be_bytes(),
+                });
+                4
+            }
+            LengthPrefix::Varint => {
+                let mut rest = len;
+                let mut used = 0;
+                loop {
+                    let low = (rest & 0x7f) as u8;
+                    rest >>= 7;
+                    if rest == 0 {
+                        buf[used] = low;
+                        break used + 1;
+                    }
+                    buf[used] = low | 0x80;
+                    used += 1;
+                }
+            }
+        };
+        Ok((buf, used))
+    }
+
+    /// Decodes a length from the start of `bytes`, returning it along with
+    /// the number of bytes it took up.
+    pub(crate) fn decode(self, bytes: &[u8]) -> Result<(usize, usize), DecodeError> {
+        let size = match self {
+            LengthPrefix::U8 => 1,
+            LengthPrefix::U16(_) => 2,
+            LengthPrefix::U32(_) => 4,
+            LengthPrefix::Varint => return decode_varint(bytes),
+        };
+        if bytes.len() < size {
+            return Err(DecodeError::TooShort { expected: size, actual: bytes.len() });
+        }
+        let len = match self {
+            LengthPrefix::U16(order) => {
+                let raw = [bytes[0], bytes[1]];
+                u64::from(match order {
+                    ByteOrder::Little => u16::from_le_bytes(raw),
+                    ByteOrder::Big => u16::from_be_bytes(raw),
+                })
+            }
+            LengthPrefix::U32(order) => {
+                let raw = [bytes[0], bytes[1], bytes[2], bytes[3]];
+                u64::from(match order {
+                    ByteOrder::Little => u32::from_le_bytes(raw),
+                    ByteOrder::Big => u32::from_be_bytes(raw),
+                })
+            }
+            _ => u64::from(bytes[0]),
+        };
+        let len = usize::try_from(len).map_err(|_| DecodeError::LengthOverflow)?;
+        Ok((len, size))
+    }
+}
+
+/// Decodes an unsigned LEB128 number from the start of `bytes`.
+fn decode_varint(bytes: &[u8]) -> Result<(usize, usize), DecodeError> {
+    let mut value: usize = 0;
+    for (i, &byte) in bytes.iter().enumerate() {
+        let low = (byte & 0x7f) as usize;
+        let shift = 7 * i as u32;
+        if shift >= usize::BITS || (low << shift) >> shift != low {
+            return Err(DecodeError::LengthOverflow);
+        }
+        value |= low << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(DecodeError::TooShort { expected: bytes.len() + 1, actual: bytes.len() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(prefix: LengthPrefix, len: usize) -> usize {
+        let (buf, used) = prefix.encode(len).unwrap();
+        assert_eq!(prefix.decode(&buf[..used]), Ok((len, used)));
+        used
+    }
+
+    #[test]
+    fn fixed_widths() {
+        assert_eq!(round_trip(LengthPrefix::U8, 200), 1);
+        assert_eq!(round_trip(LengthPrefix::U16(ByteOrder::Big), 0x1234), 2);
+        assert_eq!(LengthPrefix::U16(ByteOrder::Big).encode(0x1234).unwrap().0[..2], [0x12, 0x34]);
+        assert_eq!(round_trip(LengthPrefix::U32(ByteOrder::Little), 70000), 4);
+        assert_eq!(
+            LengthPrefix::U8.encode(256),
+            Err(WriteError::LengthOverflow { len: 256, max: 255 }),
+        );
+    }
+
+    #[test]
+    fn varints() {
+        assert_eq!(round_trip(LengthPrefix::Varint, 0), 1);
+        assert_eq!(round_trip(LengthPrefix::Varint, 127), 1);
+        assert_eq!(round_trip(LengthPrefix::Varint, 300), 2);
+        assert_eq!(LengthPrefix::Varint.encode(300).unwrap().0[..2], [0xac, 0x02]);
+        assert_eq!(round_trip(LengthPrefix::Varint, usize::MAX), usize::BITS.div_ceil(7) as usize);
+        assert_eq!(
+            LengthPrefix::Varint.decode(&[0x80, 0x80]),
+            Err(DecodeError::TooShort { expected: 3, actual: 2 }),
+        );
+        assert_eq!(LengthPrefix::Varint.decode(&[0xff; 11]), Err(DecodeError::LengthOverflow));
+    }
+}
@@ -0,0 +1,158 @@
+use core::mem;
+
+use prefix::LengthPrefix;
+use split::{split_prefix, split_prefix_slice};
+use {AnyBitPattern, DecodeError};
+
+/// A cursor which decodes values one after another from a slice of bytes.
+///
+/// Every value returned borrows from the original bytes, so nothing is
+/// copied. When a read fails, the position of the reader does not change.
+#[derive(Clone, Debug)]
+pub struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl <'a> ByteReader<'a> {
+    /// Creates a reader positioned at the start of `bytes`.
+    #[inline]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        ByteReader { bytes, pos: 0 }
+    }
+
+    /// Returns the number of bytes read or skipped so far.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the bytes which have not been read yet.
+    #[inline]
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
+
+    /// Returns whether every byte has been read.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    /// Decodes a `T` and moves past it.
+    #[inline]
+    pub fn read<T: AnyBitPattern>(&mut self) -> Result<&'a T, DecodeError> {
+        let value = self.peek()?;
+        self.pos += mem::size_of::<T>();
+        Ok(value)
+    }
+
+    /// Decodes `n` values of type `T` and moves past them. This works for
+    /// types with a size of zero too, which take up no bytes.
+    #[inline]
+    pub fn read_slice<T: AnyBitPattern>(&mut self, n: usize) -> Result<&'a [T], DecodeError> {
+        let (values, _) = split_prefix_slice(self.remaining(), n)?;
+        self.pos += mem::size_of_val(values);
+        Ok(values)
+    }
+
+    /// Decodes a length stored as `prefix`, then that many values of type
+    /// `T` after it, and moves past both.
+    pub fn read_prefixed_slice<T: AnyBitPattern>(
+        &mut self,
+        prefix: LengthPrefix,
+    ) -> Result<&'a [T], DecodeError> {
+        let (n, prefix_len) = prefix.decode(self.remaining())?;
+        let (values, _) = split_prefix_slice(&self.remaining()[prefix_len..], n)?;
+        self.pos += prefix_len + mem::size_of_val(values);
+        Ok(values)
+    }
+
+    /// Decodes a `T` without moving past it.
+    #[inline]
+    pub fn peek<T: AnyBitPattern>(&self) -> Result<&'a T, DecodeError> {
+        split_prefix(self.remaining()).map(|(value, _)| value)
+    }
+
+    /// Moves past `n` bytes.
+    #[inline]
+    pub fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        let remaining = self.bytes.len() - self.pos;
+        if n > remaining {
+            return Err(DecodeError::TooShort { expected: n, actual: remaining });
+        }
+        self.pos += n;
+        Ok(())
+    }
+
+    /// Moves forward to the next position which is a multiple of `align`,
+    /// counting from the start of the bytes.
+    ///
+    /// # Panics
+    /// This function panics if `align` is zero.
+    #[inline]
+    pub fn align_to(&mut self, align: usize) -> Result<(), DecodeError> {
+        let padding = self.pos.next_multiple_of(align) - self.pos;
+        self.skip(padding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prefix::ByteOrder;
+    use AsBytes;
+
+    #[test]
+    fn read_records() {
+        let data = [2u32, 7, 8, 0xffff_ffff];
+        let mut reader = ByteReader::new(data.as_bytes());
+
+        let count = *reader.read::<u32>().unwrap();
+        assert_eq!(reader.read_slice::<u32>(count as usize), Ok(&[7, 8][..]));
+        assert_eq!(reader.position(), 12);
+        assert_eq!(reader.peek::<u32>(), Ok(&0xffff_ffff));
+        assert_eq!(reader.read::<u16>(), Ok(&0xffff));
+        assert_eq!(
+            reader.read::<u32>(),
+            Err(DecodeError::TooShort { expected: 4, actual: 2 }),
+        );
+        assert_eq!(reader.position(), 14);
+        assert_eq!(reader.read_slice::<()>(3), Ok(&[(); 3][..]));
+        assert_eq!(reader.position(), 14);
+    }
+
+    #[test]
+    fn skip_and_align() {
+        let data = [0u64; 2];
+        let mut reader = ByteReader::new(data.as_bytes());
+
+        reader.skip(1).unwrap();
+        assert_eq!(
+            reader.read::<u32>(),
+            Err(DecodeError::Misaligned { align: 4, offset: 1 }),
+        );
+        reader.align_to(8).unwrap();
+        assert_eq!(reader.read::<u64>(), Ok(&0));
+        assert!(reader.is_empty());
+        assert_eq!(reader.align_to(16), Ok(()));
+        assert_eq!(reader.skip(1), Err(DecodeError::TooShort { expected: 1, actual: 0 }));
+    }
+
+    #[test]
+    fn prefixed_slices() {
+        let data = [2u8, 10, 20, 0, 1, 30, 0x80];
+        let mut reader = ByteReader::new(&data);
+
+        assert_eq!(reader.read_prefixed_slice::<u8>(LengthPrefix::U8), Ok(&[10, 20][..]));
+        assert_eq!(
+            reader.read_prefixed_slice::<u8>(LengthPrefix::U16(ByteOrder::Big)),
+            Ok(&[30][..]),
+        );
+        assert_eq!(
+            reader.read_prefixed_slice::<u8>(LengthPrefix::Varint),
+            Err(DecodeError::TooShort { expected: 2, actual: 1 }),
+        );
+        assert_eq!(reader.position(), 6);
+    }
+}
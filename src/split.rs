@@ -0,0 +1,185 @@
+use core::mem;
+use core::slice;
+
+use pod::BitsLayout;
+use {check_alignment, check_bit_pattern};
+use {AnyBitPattern, CheckedBitPattern, DecodeError, SafeWithBytes};
+
+/// Decodes exactly `n` values of type `T` from the start of `bytes`,
+/// ignoring any bytes after them.
+///
+/// Unlike `<[T]>::safe_with_bytes`, which works out the number of values
+/// from the length of the bytes, this also works for types with a size of
+/// zero, in which case any number of values can be decoded from any bytes
+/// which are suitably aligned.
+///
+/// ```rust
+/// use as_with_bytes::{with_bytes_n, AsBytes};
+///
+/// let arr = [1u16, 2, 3];
+/// assert_eq!(with_bytes_n::<u16>(arr.as_bytes(), 2), Ok(&[1, 2][..]));
+/// assert_eq!(with_bytes_n::<()>(&[], 5), Ok(&[(); 5][..]));
+/// ```
+#[inline]
+pub fn with_bytes_n<T: CheckedBitPattern>(bytes: &[u8], n: usize) -> Result<&[T], DecodeError> {
+    let () = BitsLayout::<T>::OK;
+    let size = slice_size::<T>(bytes, n)?;
+    if bytes.len() < size {
+        return Err(DecodeError::TooShort { expected: size, actual: bytes.len() });
+    }
+    check_alignment::<T>(bytes)?;
+    let bits = unsafe { slice::from_raw_parts(bytes.as_ptr() as *const T::Bits, n) };
+    // Values with a size of zero are all the same, so checking one is enough.
+    let distinct = if mem::size_of::<T>() == 0 { n.min(1) } else { n };
+    for bits in &bits[..distinct] {
+        check_bit_pattern::<T>(bits)?;
+    }
+    Ok(unsafe { slice::from_raw_parts(bits.as_ptr() as *const T, n) })
+}
+
+/// Decodes a `T` from the start of `bytes`, returning it along with the
+/// bytes after it, or `None` if there are too few bytes or they are
+/// misaligned.
+#[inline]
+pub fn try_split_prefix<T: AnyBitPattern>(bytes: &[u8]) -> Option<(&T, &[u8])> {
+    split_prefix(bytes).ok()
+}
+
+/// Decodes a `T` from the end of `bytes`, returning it along with the
+/// bytes before it, or `None` if there are too few bytes or they are
+/// misaligned.
+#[inline]
+pub fn try_split_suffix<T: AnyBitPattern>(bytes: &[u8]) -> Option<(&[u8], &T)> {
+    split_suffix(bytes).ok()
+}
+
+/// Decodes `n` values of type `T` from the start of `bytes`, returning them
+/// along with the bytes after them, or `None` if there are too few bytes or
+/// they are misaligned.
+#[inline]
+pub fn try_split_prefix_slice<T: AnyBitPattern>(bytes: &[u8], n: usize) -> Option<(&[T], &[u8])> {
+    split_prefix_slice(bytes, n).ok()
+}
+
+/// Decodes `n` values of type `T` from the end of `bytes`, returning them
+/// along with the bytes before them, or `None` if there are too few bytes or
+/// they are misaligned.
+#[inline]
+pub fn try_split_suffix_slice<T: AnyBitPattern>(bytes: &[u8], n: usize) -> Option<(&[u8], &[T])> {
+    split_suffix_slice(bytes, n).ok()
+}
+
+pub(crate) fn split_prefix<T: AnyBitPattern>(bytes: &[u8]) -> Result<(&T, &[u8]), DecodeError> {
+    let (head, tail) = split_bytes_at(bytes, mem::size_of::<T>())?;
+    Ok((T::safe_with_bytes(head)?, tail))
+}
+
+pub(crate) fn split_suffix<T: AnyBitPattern>(bytes: &[u8]) -> Result<(&[u8], &T), DecodeError> {
+    let (head, tail) = split_bytes_at_end(bytes, mem::size_of::<T>())?;
+    Ok((head, T::safe_with_bytes(tail)?))
+}
+
+pub(crate) fn split_prefix_slice<T: AnyBitPattern>(
+    bytes: &[u8],
+    n: usize,
+) -> Result<(&[T], &[u8]), DecodeError> {
+    let values = with_bytes_n(bytes, n)?;
+    Ok((values, &bytes[mem::size_of_val(values)..]))
+}
+
+pub(crate) fn split_suffix_slice<T: AnyBitPattern>(
+    bytes: &[u8],
+    n: usize,
+) -> Result<(&[u8], &[T]), DecodeError> {
+    let (head, tail) = split_bytes_at_end(bytes, slice_size::<T>(bytes, n)?)?;
+    Ok((head, with_bytes_n(tail, n)?))
+}
+
+/// Returns the number of bytes taken up by `n` values of type `T`.
+fn slice_size<T>(bytes: &[u8], n: usize) -> Result<usize, DecodeError> {
+    mem::size_of::<T>().checked_mul(n).ok_or(DecodeError::TooShort {
+        expected: usize::MAX,
+        actual: bytes.len(),
+    })
+}
+
+/// Splits off the first `size` bytes.
+fn split_bytes_at(bytes: &[u8], size: usize) -> Result<(&[u8], &[u8]), DecodeError> {
+    if bytes.len() < size {
+        Err(DecodeError::TooShort { expected: size, actual: bytes.len() })
+    } else {
+        Ok(bytes.split_at(size))
+    }
+}
+
+/// Splits off the last `size` bytes.
+fn split_bytes_at_end(bytes: &[u8], size: usize) -> Result<(&[u8], &[u8]), DecodeError> {
+    if bytes.len() < size {
+        Err(DecodeError::TooShort { expected: size, actual: bytes.len() })
+    } else {
+        Ok(bytes.split_at(bytes.len() - size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AsBytes;
+
+    #[test]
+    fn sequential_parsing() {
+        let packet = [3u16, 10, 20, 30, 99];
+        let bytes = packet.as_bytes();
+
+        let (&len, rest) = try_split_prefix::<u16>(bytes).unwrap();
+        let (items, rest) = try_split_prefix_slice::<u16>(rest, len as usize).unwrap();
+        let (rest, trailer) = try_split_suffix::<u16>(rest).unwrap();
+
+        assert_eq!(items, &[10, 20, 30]);
+        assert_eq!(*trailer, 99);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn suffix_slices() {
+        let arr = [1u32, 2, 3];
+        let (rest, tail) = try_split_suffix_slice::<u32>(arr.as_bytes(), 2).unwrap();
+
+        assert_eq!(rest, 1u32.as_bytes());
+        assert_eq!(tail, &[2, 3]);
+    }
+
+    #[test]
+    fn too_short_or_misaligned() {
+        let arr = [0u32; 2];
+        let bytes = arr.as_bytes();
+
+        assert_eq!(try_split_prefix::<u64>(&bytes[1..]), None);
+        assert_eq!(try_split_prefix::<u32>(&bytes[1..]), None);
+        assert_eq!(try_split_suffix_slice::<u32>(bytes, 3), None);
+        assert_eq!(try_split_prefix_slice::<u32>(bytes, usize::MAX), None);
+    }
+
+    #[test]
+    fn counted_slices() {
+        let arr = [1u32, 2, 3];
+        let bytes = arr.as_bytes();
+
+        assert_eq!(with_bytes_n::<u32>(bytes, 3), Ok(&arr[..]));
+        assert_eq!(with_bytes_n::<u32>(&bytes[..10], 2), Ok(&arr[..2]));
+        assert_eq!(
+            with_bytes_n::<u32>(bytes, 4),
+            Err(DecodeError::TooShort { expected: 16, actual: 12 }),
+        );
+        assert_eq!(
+            with_bytes_n::<u32>(&bytes[1..], 0),
+            Err(DecodeError::Misaligned { align: 4, offset: 1 }),
+        );
+        assert_eq!(with_bytes_n::<()>(bytes, usize::MAX).map(<[()]>::len), Ok(usize::MAX));
+        assert_eq!(with_bytes_n::<bool>(&[1, 2], 1), Ok(&[true][..]));
+        assert_eq!(with_bytes_n::<bool>(&[1, 2], 2), Err(DecodeError::InvalidBitPattern));
+
+        let (units, rest) = try_split_prefix_slice::<()>(bytes, 3).unwrap();
+        assert_eq!((units.len(), rest), (3, bytes));
+    }
+}
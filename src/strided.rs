@@ -0,0 +1,272 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::Index;
+
+use {check_alignment, AnyBitPattern, DecodeError};
+
+/// A view of `len` values of type `T` spaced `stride` bytes apart in a slice
+/// of bytes.
+///
+/// This describes arrays of records which are padded or interleaved with
+/// other data, such as the vertices in a vertex buffer, which `<[T]>` cannot
+/// because its elements are always `size_of::<T>()` bytes apart. Like the
+/// other ways of decoding, nothing is copied. Types with an alignment of 1,
+/// such as `Unaligned<T>` and the types in `endian`, can be used with any
+/// offset and stride.
+pub struct StridedSlice<'a, T> {
+    bytes: &'a [u8],
+    stride: usize,
+    len: usize,
+    marker: PhantomData<&'a [T]>,
+}
+
+impl <'a, T: AnyBitPattern> StridedSlice<'a, T> {
+    /// Creates a view of `len` values, the first of which starts `offset`
+    /// bytes into `bytes`, with each value starting `stride` bytes after the
+    /// previous one.
+    ///
+    /// This fails if the last value would go past the end of `bytes`, or if
+    /// any of the values would be misaligned.
+    pub fn new(
+        bytes: &'a [u8],
+        offset: usize,
+        stride: usize,
+        len: usize,
+    ) -> Result<Self, DecodeError> {
+        let needed = match len.checked_sub(1) {
+            Some(last) => last
+                .checked_mul(stride)
+                .and_then(|n| n.checked_add(mem::size_of::<T>()))
+                .and_then(|n| n.checked_add(offset)),
+            None => Some(offset),
+        };
+        let needed = needed.unwrap_or(usize::MAX);
+        if needed > bytes.len() {
+            return Err(DecodeError::TooShort { expected: needed, actual: bytes.len() });
+        }
+        let bytes = &bytes[offset..];
+        if len > 0 {
+            check_alignment::<T>(bytes)?;
+        }
+        if len > 1 {
+            let align = mem::align_of::<T>();
+            let remainder = stride % align;
+            if remainder != 0 {
+                return Err(DecodeError::Misaligned { align, offset: remainder });
+            }
+        }
+        Ok(StridedSlice { bytes, stride, len, marker: PhantomData })
+    }
+
+    /// Returns the number of values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether there are no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bytes from the start of one value to the start
+    /// of the next.
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the value at `index`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index < self.len {
+            // `new` checked that every value is in bounds and aligned.
+            Some(unsafe { &*(self.bytes.as_ptr().add(index * self.stride) as *const T) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the first value, or `None` if there are none.
+    #[inline]
+    pub fn first(&self) -> Option<&'a T> {
+        self.get(0)
+    }
+
+    /// Returns the last value, or `None` if there are none.
+    #[inline]
+    pub fn last(&self) -> Option<&'a T> {
+        self.len.checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// Returns an iterator over the values.
+    #[inline]
+    pub fn iter(&self) -> StridedIter<'a, T> {
+        StridedIter { slice: *self, front: 0, back: self.len }
+    }
+}
+
+impl <T> Clone for StridedSlice<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl <T> Copy for StridedSlice<'_, T> {}
+
+impl <T: AnyBitPattern + fmt::Debug> fmt::Debug for StridedSlice<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl <T: AnyBitPattern> Index<usize> for StridedSlice<'_, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!("index {} out of bounds for length {}", index, self.len),
+        }
+    }
+}
+
+impl <'a, T: AnyBitPattern> IntoIterator for StridedSlice<'a, T> {
+    type Item = &'a T;
+    type IntoIter = StridedIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> StridedIter<'a, T> {
+        self.iter()
+    }
+}
+
+impl <'a, T: AnyBitPattern> IntoIterator for &StridedSlice<'a, T> {
+    type Item = &'a T;
+    type IntoIter = StridedIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> StridedIter<'a, T> {
+        self.iter()
+    }
+}
+
+/// An iterator over the values of a `StridedSlice`.
+pub struct StridedIter<'a, T> {
+    slice: StridedSlice<'a, T>,
+    front: usize,
+    back: usize,
+}
+
+impl <T> Clone for StridedIter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        StridedIter { slice: self.slice, front: self.front, back: self.back }
+    }
+}
+
+impl <T: AnyBitPattern + fmt::Debug> fmt::Debug for StridedIter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl <'a, T: AnyBitPattern> Iterator for StridedIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        let value = self.slice.get(self.front);
+        self.front += 1;
+        value
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl <T: AnyBitPattern> DoubleEndedIterator for StridedIter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.slice.get(self.back)
+    }
+}
+
+impl <T: AnyBitPattern> ExactSizeIterator for StridedIter<'_, T> {}
+
+impl <T: AnyBitPattern> FusedIterator for StridedIter<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use endian::{F32Le, U16Be};
+    use {AsBytes, Unaligned};
+
+    #[test]
+    fn interleaved_records() {
+        // Each record is a position followed by a color which is skipped.
+        let data = [1u32, 0xff, 2, 0xff, 3, 0xff];
+        let positions = StridedSlice::<u32>::new(data.as_bytes(), 0, 8, 3).unwrap();
+
+        assert_eq!(positions.len(), 3);
+        assert_eq!(positions[1], 2);
+        assert_eq!(positions.get(3), None);
+        assert!(positions.iter().copied().eq([1, 2, 3]));
+        assert!(positions.iter().rev().copied().eq([3, 2, 1]));
+
+        let colors = StridedSlice::<u32>::new(data.as_bytes(), 4, 8, 3).unwrap();
+        assert!(colors.into_iter().all(|&c| c == 0xff));
+        assert_eq!(
+            StridedSlice::<u32>::new(data.as_bytes(), 4, 8, 4).map(|s| s.len()),
+            Err(DecodeError::TooShort { expected: 32, actual: 24 }),
+        );
+    }
+
+    #[test]
+    fn alignment() {
+        let data = [0u32; 4];
+
+        assert_eq!(
+            StridedSlice::<u32>::new(data.as_bytes(), 2, 4, 1).map(|s| s.len()),
+            Err(DecodeError::Misaligned { align: 4, offset: 2 }),
+        );
+        assert_eq!(
+            StridedSlice::<u32>::new(data.as_bytes(), 0, 6, 2).map(|s| s.len()),
+            Err(DecodeError::Misaligned { align: 4, offset: 2 }),
+        );
+        assert_eq!(StridedSlice::<u32>::new(data.as_bytes(), 0, 6, 1).map(|s| s.len()), Ok(1));
+    }
+
+    #[test]
+    fn packed_fields() {
+        // Records of a one-byte tag, a big-endian u16 and a little-endian f32.
+        let mut data = [0u8; 14];
+        data[1..3].copy_from_slice(&[0x01, 0x02]);
+        data[3..7].copy_from_slice(&1.5f32.to_le_bytes());
+        data[8..10].copy_from_slice(&[0x03, 0x04]);
+        data[10..14].copy_from_slice(&(-2f32).to_le_bytes());
+
+        let ids = StridedSlice::<U16Be>::new(&data, 1, 7, 2).unwrap();
+        let weights = StridedSlice::<F32Le>::new(&data, 3, 7, 2).unwrap();
+        let raw = StridedSlice::<Unaligned<u16>>::new(&data, 1, 7, 2).unwrap();
+
+        assert!(ids.iter().map(|id| id.get()).eq([0x0102, 0x0304]));
+        assert!(weights.iter().map(|w| w.get()).eq([1.5, -2.0]));
+        assert_eq!(raw[1].get().as_bytes(), &[0x03, 0x04]);
+    }
+}
@@ -0,0 +1,243 @@
+use core::ffi::CStr;
+use core::fmt;
+use core::str;
+
+use {
+    AnyBitPattern, AsBytes, CheckedWithBytes, DecodeError, NoUninit, SafeWithBytes, TryWithBytes,
+    WithBytes,
+};
+
+impl AsBytes for str {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+}
+
+impl WithBytes for str {
+    /// Returns the bytes as a string without checking that they are valid
+    /// UTF-8.
+    ///
+    /// # Safety
+    /// The bytes must be valid UTF-8.
+    #[inline]
+    unsafe fn with_bytes(bytes: &[u8]) -> &str {
+        str::from_utf8_unchecked(bytes)
+    }
+}
+
+impl TryWithBytes for str {
+    #[inline]
+    unsafe fn try_with_bytes(bytes: &[u8]) -> Option<&str> {
+        str::safe_with_bytes(bytes).ok()
+    }
+}
+
+impl CheckedWithBytes for str {
+    #[inline]
+    unsafe fn checked_with_bytes(bytes: &[u8]) -> Result<&str, DecodeError> {
+        str::safe_with_bytes(bytes)
+    }
+
+    #[inline]
+    unsafe fn checked_with_bytes_exact(bytes: &[u8]) -> Result<&str, DecodeError> {
+        str::safe_with_bytes(bytes)
+    }
+}
+
+impl SafeWithBytes for str {
+    /// Returns the bytes as a string, or `DecodeError::InvalidUtf8` if they
+    /// are not valid UTF-8. Every byte is part of the string, so there are
+    /// never any trailing bytes.
+    #[inline]
+    fn safe_with_bytes(bytes: &[u8]) -> Result<&str, DecodeError> {
+        str::from_utf8(bytes).map_err(|e| DecodeError::InvalidUtf8 { valid_up_to: e.valid_up_to() })
+    }
+
+    #[inline]
+    fn safe_with_bytes_exact(bytes: &[u8]) -> Result<&str, DecodeError> {
+        str::safe_with_bytes(bytes)
+    }
+}
+
+impl AsBytes for CStr {
+    /// Returns the bytes of the string including the nul terminator.
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self.to_bytes_with_nul()
+    }
+}
+
+impl TryWithBytes for CStr {
+    #[inline]
+    unsafe fn try_with_bytes(bytes: &[u8]) -> Option<&CStr> {
+        CStr::safe_with_bytes(bytes).ok()
+    }
+}
+
+impl CheckedWithBytes for CStr {
+    #[inline]
+    unsafe fn checked_with_bytes(bytes: &[u8]) -> Result<&CStr, DecodeError> {
+        CStr::safe_with_bytes(bytes)
+    }
+
+    #[inline]
+    unsafe fn checked_with_bytes_exact(bytes: &[u8]) -> Result<&CStr, DecodeError> {
+        CStr::safe_with_bytes_exact(bytes)
+    }
+}
+
+impl SafeWithBytes for CStr {
+    /// Returns the string ending at the first nul byte, or
+    /// `DecodeError::MissingNul` if there is none.
+    #[inline]
+    fn safe_with_bytes(bytes: &[u8]) -> Result<&CStr, DecodeError> {
+        CStr::from_bytes_until_nul(bytes).map_err(|_| DecodeError::MissingNul)
+    }
+
+    /// Works like `safe_with_bytes`, but fails with
+    /// `DecodeError::TrailingBytes` unless the only nul byte is the last one.
+    #[inline]
+    fn safe_with_bytes_exact(bytes: &[u8]) -> Result<&CStr, DecodeError> {
+        let s = CStr::safe_with_bytes(bytes)?;
+        let len = s.to_bytes_with_nul().len();
+        if len < bytes.len() {
+            return Err(DecodeError::TrailingBytes { expected: len, actual: bytes.len() });
+        }
+        Ok(s)
+    }
+}
+
+/// A string stored in a fixed array of `N` bytes, with unused bytes at the
+/// end set to zero.
+///
+/// This is the layout of a `char name[N]` field in a C struct. The string
+/// ends at the first nul byte, or fills the whole array if there is none.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct NulPaddedStr<const N: usize>([u8; N]);
+
+impl <const N: usize> NulPaddedStr<N> {
+    /// Copies `s` into a new array, padding it with zeroes. Returns `None`
+    /// if `s` is longer than `N` bytes or contains a nul byte.
+    pub fn new(s: &str) -> Option<Self> {
+        let bytes = str::as_bytes(s);
+        if bytes.len() > N || bytes.contains(&0) {
+            return None;
+        }
+        let mut array = [0; N];
+        array[..bytes.len()].copy_from_slice(bytes);
+        Some(NulPaddedStr(array))
+    }
+
+    /// Wraps an array of bytes as it is.
+    #[inline]
+    pub fn from_array(array: [u8; N]) -> Self {
+        NulPaddedStr(array)
+    }
+
+    /// Returns the whole array, including the padding.
+    #[inline]
+    pub fn into_array(self) -> [u8; N] {
+        self.0
+    }
+
+    /// Returns the bytes of the string, without the padding.
+    #[inline]
+    pub fn to_bytes(&self) -> &[u8] {
+        let len = self.0.iter().position(|&b| b == 0).unwrap_or(N);
+        &self.0[..len]
+    }
+
+    /// Returns the string, or `DecodeError::InvalidUtf8` if it is not valid
+    /// UTF-8.
+    #[inline]
+    pub fn to_str(&self) -> Result<&str, DecodeError> {
+        str::safe_with_bytes(self.to_bytes())
+    }
+
+    /// Returns the string as a `CStr`, or `DecodeError::MissingNul` if it
+    /// fills the whole array and so has no nul terminator.
+    #[inline]
+    pub fn to_c_str(&self) -> Result<&CStr, DecodeError> {
+        CStr::safe_with_bytes(&self.0)
+    }
+}
+
+impl <const N: usize> Default for NulPaddedStr<N> {
+    #[inline]
+    fn default() -> Self {
+        NulPaddedStr([0; N])
+    }
+}
+
+impl <const N: usize> fmt::Debug for NulPaddedStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_str() {
+            Ok(s) => fmt::Debug::fmt(s, f),
+            Err(_) => fmt::Debug::fmt(self.to_bytes(), f),
+        }
+    }
+}
+
+unsafe impl <const N: usize> AnyBitPattern for NulPaddedStr<N> {}
+
+unsafe impl <const N: usize> NoUninit for NulPaddedStr<N> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AsBytesMut;
+
+    #[test]
+    fn strings() {
+        assert_eq!("hé".as_bytes(), &[b'h', 0xc3, 0xa9]);
+        assert_eq!(str::safe_with_bytes(b"abc"), Ok("abc"));
+        assert_eq!(
+            str::safe_with_bytes(&[b'a', 0xc3]),
+            Err(DecodeError::InvalidUtf8 { valid_up_to: 1 }),
+        );
+        assert_eq!(unsafe { str::try_with_bytes(&[0xff]) }, None);
+    }
+
+    #[test]
+    fn c_strings() {
+        let bytes = b"name\0\0\0";
+
+        assert_eq!(CStr::safe_with_bytes(bytes).map(CStr::to_bytes), Ok(&b"name"[..]));
+        assert_eq!(
+            CStr::safe_with_bytes_exact(bytes),
+            Err(DecodeError::TrailingBytes { expected: 5, actual: 7 }),
+        );
+        assert_eq!(CStr::safe_with_bytes(b"name"), Err(DecodeError::MissingNul));
+        assert_eq!(CStr::safe_with_bytes_exact(&bytes[..5]).map(AsBytes::as_bytes), Ok(&bytes[..5]));
+    }
+
+    #[test]
+    fn nul_padded() {
+        #[derive(Clone, Copy)]
+        #[repr(C)]
+        struct Record {
+            id: u32,
+            name: NulPaddedStr<8>,
+        }
+
+        unsafe impl AnyBitPattern for Record {}
+
+        let mut bytes = [0u32; 3];
+        bytes[0] = 7;
+        bytes[1..].as_bytes_mut().copy_from_slice(b"widget\0\0");
+        let record = Record::safe_with_bytes(bytes.as_bytes()).unwrap();
+
+        assert_eq!(record.id, 7);
+        assert_eq!(record.name.to_str(), Ok("widget"));
+        assert_eq!(record.name.to_c_str().map(CStr::to_bytes), Ok(&b"widget"[..]));
+        assert_eq!(NulPaddedStr::<8>::new("widget"), Some(record.name));
+
+        let full = NulPaddedStr::<4>::new("abcd").unwrap();
+        assert_eq!(full.to_str(), Ok("abcd"));
+        assert_eq!(full.to_c_str(), Err(DecodeError::MissingNul));
+        assert_eq!(NulPaddedStr::<4>::new("abcde"), None);
+        assert_eq!(NulPaddedStr::<4>::new("a\0b"), None);
+    }
+}
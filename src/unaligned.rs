@@ -0,0 +1,117 @@
+use core::fmt;
+use core::ptr;
+
+use {AnyBitPattern, NoUninit};
+
+/// A `T` with an alignment of 1.
+///
+/// Since any address is suitably aligned for it, an `Unaligned<T>` can be
+/// decoded from any offset in a slice of bytes, which makes it useful for
+/// describing the fields of packed structs. The value is read and written
+/// with `get` and `set`, which perform unaligned memory accesses.
+#[repr(C, packed)]
+pub struct Unaligned<T: Copy>(T);
+
+impl <T: Copy> Unaligned<T> {
+    /// Wraps `value`.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Unaligned(value)
+    }
+
+    /// Returns a copy of the wrapped value.
+    #[inline]
+    pub fn get(&self) -> T {
+        unsafe { ptr::read_unaligned(ptr::addr_of!(self.0)) }
+    }
+
+    /// Replaces the wrapped value.
+    #[inline]
+    pub fn set(&mut self, value: T) {
+        unsafe { ptr::write_unaligned(ptr::addr_of_mut!(self.0), value) }
+    }
+}
+
+impl <T: Copy> Clone for Unaligned<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl <T: Copy> Copy for Unaligned<T> {}
+
+impl <T: Copy + Default> Default for Unaligned<T> {
+    #[inline]
+    fn default() -> Self {
+        Unaligned::new(T::default())
+    }
+}
+
+impl <T: Copy + PartialEq> PartialEq for Unaligned<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl <T: Copy + Eq> Eq for Unaligned<T> {}
+
+impl <T: Copy + fmt::Debug> fmt::Debug for Unaligned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Unaligned").field(&self.get()).finish()
+    }
+}
+
+impl <T: Copy> From<T> for Unaligned<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Unaligned::new(value)
+    }
+}
+
+unsafe impl <T: AnyBitPattern> AnyBitPattern for Unaligned<T> {}
+
+unsafe impl <T: NoUninit> NoUninit for Unaligned<T> {}
+
+#[cfg(test)]
+mod tests {
+    use core::mem;
+    use super::*;
+    use {AsBytes, SafeWithBytesMut, SafeWithBytes};
+
+    #[test]
+    fn alignment_is_one() {
+        assert_eq!(mem::align_of::<Unaligned<u64>>(), 1);
+        assert_eq!(mem::size_of::<Unaligned<u64>>(), 8);
+    }
+
+    #[test]
+    fn decode_at_odd_offset() {
+        let bytes = [0u8, 1, 2, 3, 4, 5, 6];
+
+        for offset in 0..4 {
+            let expected = u32::from_ne_bytes([
+                bytes[offset],
+                bytes[offset + 1],
+                bytes[offset + 2],
+                bytes[offset + 3],
+            ]);
+
+            assert_eq!(
+                Unaligned::<u32>::safe_with_bytes(&bytes[offset..]).map(Unaligned::get),
+                Ok(expected),
+            );
+        }
+    }
+
+    #[test]
+    fn set_through_bytes() {
+        let mut bytes = [0u8; 7];
+        let field = Unaligned::<u32>::safe_with_bytes_mut(&mut bytes[1..5]).unwrap();
+
+        field.set(0xdead_beef);
+        assert_eq!(field.get(), 0xdead_beef);
+        assert_eq!(&bytes[1..5], 0xdead_beefu32.as_bytes());
+    }
+}
@@ -0,0 +1,352 @@
+#[cfg(feature = "alloc")]
+use core::fmt;
+use core::mem;
+
+#[cfg(feature = "alloc")]
+use buf::{Align8, AlignedBuf};
+use prefix::LengthPrefix;
+use {AnyBitPattern, AsBytes, DecodeError, NoUninit, SafeWithBytesMut, WriteError};
+
+/// A cursor which writes values one after another into a slice of bytes.
+///
+/// When a write fails, nothing is written and the position of the writer
+/// does not change.
+#[derive(Debug)]
+pub struct ByteWriter<'a> {
+    bytes: &'a mut [u8],
+    pos: usize,
+}
+
+impl <'a> ByteWriter<'a> {
+    /// Creates a writer positioned at the start of `bytes`.
+    #[inline]
+    pub fn new(bytes: &'a mut [u8]) -> Self {
+        ByteWriter { bytes, pos: 0 }
+    }
+
+    /// Returns the number of bytes written so far.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of bytes which can still be written.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    /// Writes the bytes of `value`.
+    #[inline]
+    pub fn push<T: NoUninit>(&mut self, value: &T) -> Result<(), WriteError> {
+        self.push_bytes(value.as_bytes())
+    }
+
+    /// Writes the bytes of every value in `values`.
+    #[inline]
+    pub fn push_slice<T: NoUninit>(&mut self, values: &[T]) -> Result<(), WriteError> {
+        self.push_bytes(values.as_bytes())
+    }
+
+    /// Writes the number of values in `values` as `prefix`, followed by the
+    /// bytes of the values.
+    pub fn push_prefixed_slice<T: NoUninit>(
+        &mut self,
+        values: &[T],
+        prefix: LengthPrefix,
+    ) -> Result<(), WriteError> {
+        let (prefix_buf, prefix_len) = prefix.encode(values.len())?;
+        let bytes = values.as_bytes();
+        let total = prefix_len + bytes.len();
+        if total > self.remaining() {
+            return Err(WriteError::TooShort { expected: total, actual: self.remaining() });
+        }
+        self.push_bytes(&prefix_buf[..prefix_len])?;
+        self.push_bytes(bytes)
+    }
+
+    /// Writes zero bytes until the position is a multiple of `align`.
+    ///
+    /// # Panics
+    /// This function panics if `align` is zero.
+    #[inline]
+    pub fn pad_to_align(&mut self, align: usize) -> Result<(), WriteError> {
+        let padding = self.pos.next_multiple_of(align) - self.pos;
+        self.zeroes(padding).map(|_| ())
+    }
+
+    /// Writes a zeroed `T` and returns a reference to it, so that it can be
+    /// filled in afterwards.
+    ///
+    /// To fill it in after writing more values, remember the `position`
+    /// before reserving and use `get_mut` later.
+    #[inline]
+    pub fn reserve<T: AnyBitPattern + NoUninit>(&mut self) -> Result<&mut T, WriteError> {
+        check_write_alignment::<T>(self.bytes[self.pos..].as_ptr())?;
+        let bytes = self.zeroes(mem::size_of::<T>())?;
+        Ok(T::safe_with_bytes_mut(bytes).unwrap())
+    }
+
+    /// Returns a mutable reference to a `T` which was already written at
+    /// `offset`.
+    #[inline]
+    pub fn get_mut<T: AnyBitPattern + NoUninit>(&mut self, offset: usize) -> Result<&mut T, DecodeError> {
+        get_written_mut(&mut self.bytes[..self.pos], offset)
+    }
+
+    /// Returns the bytes written so far.
+    #[inline]
+    pub fn finish(self) -> &'a [u8] {
+        &self.bytes[..self.pos]
+    }
+
+    /// Writes `bytes` at the current position.
+    fn push_bytes(&mut self, bytes: &[u8]) -> Result<(), WriteError> {
+        self.pos += bytes.write_to_prefix(&mut self.bytes[self.pos..])?;
+        Ok(())
+    }
+
+    /// Writes `n` zero bytes at the current position and returns them.
+    fn zeroes(&mut self, n: usize) -> Result<&mut [u8], WriteError> {
+        let start = self.pos;
+        if n > self.remaining() {
+            return Err(WriteError::TooShort { expected: n, actual: self.remaining() });
+        }
+        self.pos += n;
+        let bytes = &mut self.bytes[start..self.pos];
+        for byte in bytes.iter_mut() {
+            *byte = 0;
+        }
+        Ok(bytes)
+    }
+}
+
+/// A growable counterpart of `ByteWriter` which writes into an
+/// `AlignedBuf<A>`.
+///
+/// Writes only fail when reserving a value at a misaligned position or when
+/// a slice is too long for its length prefix.
+#[cfg(feature = "alloc")]
+pub struct AlignedBufWriter<A: AnyBitPattern + NoUninit = Align8> {
+    buf: AlignedBuf<A>,
+}
+
+#[cfg(feature = "alloc")]
+impl <A: AnyBitPattern + NoUninit> AlignedBufWriter<A> {
+    /// Creates a writer with an empty buffer.
+    #[inline]
+    pub fn new() -> Self {
+        AlignedBufWriter { buf: AlignedBuf::new() }
+    }
+
+    /// Creates a writer which appends to the end of `buf`.
+    #[inline]
+    pub fn with_buf(buf: AlignedBuf<A>) -> Self {
+        AlignedBufWriter { buf }
+    }
+
+    /// Returns the number of bytes written so far.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Writes the bytes of `value`.
+    #[inline]
+    pub fn push<T: NoUninit>(&mut self, value: &T) {
+        self.buf.extend_from_slice(value.as_bytes());
+    }
+
+    /// Writes the bytes of every value in `values`.
+    #[inline]
+    pub fn push_slice<T: NoUninit>(&mut self, values: &[T]) {
+        self.buf.extend_from_slice(values.as_bytes());
+    }
+
+    /// Writes the number of values in `values` as `prefix`, followed by the
+    /// bytes of the values.
+    pub fn push_prefixed_slice<T: NoUninit>(
+        &mut self,
+        values: &[T],
+        prefix: LengthPrefix,
+    ) -> Result<(), WriteError> {
+        let (prefix_buf, prefix_len) = prefix.encode(values.len())?;
+        self.buf.extend_from_slice(&prefix_buf[..prefix_len]);
+        self.buf.extend_from_slice(values.as_bytes());
+        Ok(())
+    }
+
+    /// Writes zero bytes until the position is a multiple of `align`.
+    ///
+    /// # Panics
+    /// This function panics if `align` is zero.
+    #[inline]
+    pub fn pad_to_align(&mut self, align: usize) {
+        let len = self.buf.len();
+        self.buf.resize(len.next_multiple_of(align), 0);
+    }
+
+    /// Writes a zeroed `T` and returns a reference to it, so that it can be
+    /// filled in afterwards.
+    ///
+    /// This fails if the position is not suitably aligned for `T`, which
+    /// can happen even right after `pad_to_align` if `T` is more strictly
+    /// aligned than `A`. The alignment is checked after the buffer grows,
+    /// since growing it can move the bytes to a different address.
+    pub fn reserve<T: AnyBitPattern + NoUninit>(&mut self) -> Result<&mut T, WriteError> {
+        let start = self.buf.len();
+        self.buf.resize(start + mem::size_of::<T>(), 0);
+        let ptr = self.buf[start..].as_mut_ptr();
+        if let Err(err) = check_write_alignment::<T>(ptr) {
+            self.buf.truncate(start);
+            return Err(err);
+        }
+        Ok(unsafe { &mut *(ptr as *mut T) })
+    }
+
+    /// Returns a mutable reference to a `T` which was already written at
+    /// `offset`.
+    #[inline]
+    pub fn get_mut<T: AnyBitPattern + NoUninit>(&mut self, offset: usize) -> Result<&mut T, DecodeError> {
+        get_written_mut(&mut self.buf, offset)
+    }
+
+    /// Returns the bytes written so far.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Returns the buffer holding the bytes written.
+    #[inline]
+    pub fn finish(self) -> AlignedBuf<A> {
+        self.buf
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <A: AnyBitPattern + NoUninit> Default for AlignedBufWriter<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <A: AnyBitPattern + NoUninit> Clone for AlignedBufWriter<A> {
+    fn clone(&self) -> Self {
+        AlignedBufWriter { buf: self.buf.clone() }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <A: AnyBitPattern + NoUninit> fmt::Debug for AlignedBufWriter<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AlignedBufWriter").field("buf", &self.buf).finish()
+    }
+}
+
+/// Checks that `ptr` is a suitable address for a `T`.
+#[inline]
+fn check_write_alignment<T>(ptr: *const u8) -> Result<(), WriteError> {
+    let align = mem::align_of::<T>();
+    let offset = ptr as usize % align;
+    if offset == 0 {
+        Ok(())
+    } else {
+        Err(WriteError::Misaligned { align, offset })
+    }
+}
+
+/// Decodes a `T` at `offset` in the bytes written so far.
+#[inline]
+fn get_written_mut<T: AnyBitPattern + NoUninit>(
+    written: &mut [u8],
+    offset: usize,
+) -> Result<&mut T, DecodeError> {
+    if offset > written.len() {
+        return Err(DecodeError::TooShort { expected: offset, actual: written.len() });
+    }
+    T::safe_with_bytes_mut(&mut written[offset..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {AsBytesMut, SafeWithBytes};
+
+    #[test]
+    fn write_records() {
+        let mut storage = [u32::MAX; 4];
+        let mut writer = ByteWriter::new(storage.as_bytes_mut());
+
+        writer.push(&1u8).unwrap();
+        writer.pad_to_align(4).unwrap();
+        *writer.reserve::<u32>().unwrap() = 5;
+        writer.push_slice(&[6u16, 7]).unwrap();
+        assert_eq!(
+            writer.push(&0u64),
+            Err(WriteError::TooShort { expected: 8, actual: 4 }),
+        );
+        *writer.get_mut::<u16>(8).unwrap() += 10;
+
+        let written = writer.finish();
+        assert_eq!(written.len(), 12);
+        assert_eq!(written[..4], [1, 0, 0, 0]);
+        assert_eq!(<[u16]>::safe_with_bytes(&written[8..]), Ok(&[16, 7][..]));
+        assert_eq!(storage[1], 5);
+        assert_eq!(storage[3], u32::MAX);
+    }
+
+    #[test]
+    fn reserve_misaligned() {
+        let mut storage = [0u32; 2];
+        let mut writer = ByteWriter::new(storage.as_bytes_mut());
+
+        writer.push(&0u16).unwrap();
+        assert_eq!(
+            writer.reserve::<u32>().map(|n| *n),
+            Err(WriteError::Misaligned { align: 4, offset: 2 }),
+        );
+        assert_eq!(writer.position(), 2);
+    }
+
+    #[test]
+    fn prefixed_slices() {
+        let mut storage = [0u8; 8];
+        let mut writer = ByteWriter::new(&mut storage);
+
+        writer.push_prefixed_slice(&[1u8, 2], LengthPrefix::U8).unwrap();
+        writer.push_prefixed_slice(&[3u8], LengthPrefix::Varint).unwrap();
+        assert_eq!(
+            writer.push_prefixed_slice(&[4u8, 5, 6], LengthPrefix::U8),
+            Err(WriteError::TooShort { expected: 4, actual: 3 }),
+        );
+        assert_eq!(writer.finish(), &[2, 1, 2, 1, 3]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn growable() {
+        let mut writer = AlignedBufWriter::<Align8>::new();
+
+        writer.push(&3u8);
+        writer.pad_to_align(8);
+        writer.reserve::<u64>().unwrap();
+        writer.push_slice(&[1u32, 2, 3]);
+        *writer.get_mut::<u64>(8).unwrap() = 3;
+
+        let buf = writer.finish();
+        assert_eq!(buf.len(), 28);
+        assert_eq!(buf.view_slice::<u64>().map(|n| n[1]), Ok(3));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn default_writer() {
+        let mut writer: AlignedBufWriter = AlignedBufWriter::default();
+        writer.push_slice(&[1u8, 2]);
+        assert_eq!(alloc::format!("{:?}", writer), "AlignedBufWriter { buf: [1, 2] }");
+        assert_eq!(writer.clone().as_slice(), &[1, 2]);
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(feature = "alloc")]
+use alloc::alloc::{alloc_zeroed, handle_alloc_error, Layout};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::mem;
+
+use {check_alignment, DecodeError, Zeroable};
+
+/// Sets every value in `values` to zero.
+#[inline]
+pub fn zero_slice<T: Zeroable>(values: &mut [T]) {
+    for value in values {
+        *value = T::zeroed();
+    }
+}
+
+/// Decodes a `T` from the start of `bytes` as long as the bytes it takes up
+/// are all zero.
+///
+/// Since an all-zero `T` is valid, this is safe even for types which are
+/// not valid for every bit pattern, such as `bool` or structs containing
+/// raw pointers. Bytes which are not zero are rejected with
+/// `DecodeError::InvalidBitPattern`.
+///
+/// `T` must be `Copy`, which rules out types with interior mutability.
+/// Those could otherwise be used to write to bytes which were only
+/// borrowed for reading:
+///
+/// ```compile_fail
+/// use as_with_bytes::{with_zeroed_bytes, Zeroable};
+/// use std::sync::atomic::AtomicU32;
+///
+/// struct Counter(AtomicU32);
+///
+/// unsafe impl Zeroable for Counter {}
+///
+/// let bytes = [0u8; 4];
+/// let _ = with_zeroed_bytes::<Counter>(&bytes);
+/// ```
+#[inline]
+pub fn with_zeroed_bytes<T: Zeroable + Copy + 'static>(bytes: &[u8]) -> Result<&T, DecodeError> {
+    let size = mem::size_of::<T>();
+    if bytes.len() < size {
+        return Err(DecodeError::TooShort { expected: size, actual: bytes.len() });
+    }
+    check_alignment::<T>(bytes)?;
+    if bytes[..size].iter().any(|&b| b != 0) {
+        return Err(DecodeError::InvalidBitPattern);
+    }
+    Ok(unsafe { &*(bytes.as_ptr() as *const T) })
+}
+
+/// Allocates a zeroed `T` on the heap.
+///
+/// Unlike `Box::new(T::zeroed())`, this never places the value on the
+/// stack, so it works for values too large to fit there.
+#[cfg(feature = "alloc")]
+pub fn zeroed_box<T: Zeroable>() -> Box<T> {
+    let layout = Layout::new::<T>();
+    if layout.size() == 0 {
+        return Box::new(T::zeroed());
+    }
+    unsafe {
+        let ptr = alloc_zeroed(layout) as *mut T;
+        if ptr.is_null() {
+            handle_alloc_error(layout);
+        }
+        Box::from_raw(ptr)
+    }
+}
+
+/// Allocates a vector of `len` zeroed values.
+///
+/// # Panics
+/// This function panics if the values would take up more than `isize::MAX`
+/// bytes.
+#[cfg(feature = "alloc")]
+pub fn zeroed_vec<T: Zeroable>(len: usize) -> Vec<T> {
+    let layout = Layout::array::<T>(len).expect("capacity overflow");
+    if layout.size() == 0 {
+        let mut values = Vec::with_capacity(len);
+        values.resize_with(len, T::zeroed);
+        return values;
+    }
+    unsafe {
+        let ptr = alloc_zeroed(layout) as *mut T;
+        if ptr.is_null() {
+            handle_alloc_error(layout);
+        }
+        Vec::from_raw_parts(ptr, len, len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ptr;
+    use super::*;
+    use AsBytes;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Node {
+        next: *const Node,
+        live: bool,
+    }
+
+    unsafe impl Zeroable for Node {}
+
+    #[test]
+    fn zeroed_values() {
+        assert_eq!(u64::zeroed(), 0);
+        assert_eq!((bool::zeroed(), char::zeroed()), (false, '\0'));
+        assert_eq!(Node::zeroed(), Node { next: ptr::null(), live: false });
+
+        let mut values = [1u16, 2, 3];
+        zero_slice(&mut values[1..]);
+        assert_eq!(values, [1, 0, 0]);
+    }
+
+    #[test]
+    fn zeroed_bytes() {
+        let words = [0u32, 0, 1];
+        let bytes = words.as_bytes();
+        let zeros = [0u64; 2];
+
+        assert_eq!(with_zeroed_bytes::<Node>(zeros.as_bytes()).map(|node| node.live), Ok(false));
+        assert_eq!(with_zeroed_bytes::<char>(&bytes[4..]), Ok(&'\0'));
+        assert_eq!(
+            with_zeroed_bytes::<char>(&bytes[8..]),
+            Err(DecodeError::InvalidBitPattern),
+        );
+        assert_eq!(
+            with_zeroed_bytes::<u32>(&bytes[2..]),
+            Err(DecodeError::Misaligned { align: 4, offset: 2 }),
+        );
+        assert_eq!(
+            with_zeroed_bytes::<u64>(&bytes[8..]),
+            Err(DecodeError::TooShort { expected: 8, actual: 4 }),
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn zeroed_allocations() {
+        let big = zeroed_box::<[u64; 1 << 16]>();
+        assert!(big.iter().all(|&n| n == 0));
+        assert_eq!(*zeroed_box::<()>(), ());
+
+        let nodes = zeroed_vec::<Node>(3);
+        assert_eq!(nodes.len(), 3);
+        assert!(nodes.iter().all(|node| node.next.is_null() && !node.live));
+        assert_eq!(zeroed_vec::<()>(5).len(), 5);
+    }
+}